@@ -0,0 +1,57 @@
+/// Generates the table of the in-process solution registry (see `src/template/registry.rs`).
+/// Nothing is generated unless the `registry` feature is enabled.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/bin");
+
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        return;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    // NOTE: every solution declares the dhat allocator for its binary. Compiled into the library, they would clash,
+    // so the registry stays empty and all days run as binaries while profiling.
+    let is_profiling_heap = env::var_os("CARGO_FEATURE_DHAT_HEAP").is_some();
+
+    // NOTE: only pick up files that follow the scaffolded `NN.rs` naming scheme.
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter_map(|name| name.strip_suffix(".rs").map(str::to_string))
+                .filter(|stem| {
+                    stem.len() == 2 && stem.parse::<u8>().is_ok_and(|day| (1..=25).contains(&day))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if is_profiling_heap {
+        days.clear();
+    }
+
+    days.sort_unstable();
+
+    let mut registry = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day}.rs"));
+        registry.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {path:?}]\nmod day_{day};\n\n"
+        ));
+    }
+
+    registry.push_str("/// All scaffolded solutions, sorted by day.\n");
+    registry.push_str("pub static SOLUTIONS: &[Solution] = &[\n");
+    for day in &days {
+        registry.push_str(&format!("    day_{day}::SOLUTION,\n"));
+    }
+    registry.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
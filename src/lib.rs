@@ -1,3 +1,7 @@
 pub mod template;
 
+// NOTE: lets solutions that are compiled into the registry refer to this crate by name.
+#[cfg(feature = "registry")]
+extern crate self as advent_of_code;
+
 // Use this file to add helper functions and additional modules.
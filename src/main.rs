@@ -1,14 +1,17 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, stars, time};
+use advent_of_code::template::config;
 use args::{AppArguments, parse};
+use std::process;
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        Day, ExportTarget, Pinning, ReportTarget, commands::time, parse_profiles,
+    };
+    use std::path::PathBuf;
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
@@ -21,21 +24,25 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            overwrite_data: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            time: bool,
+            pinning: Pinning,
         },
         All {
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+            reports: Vec<ReportTarget>,
         },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
-        },
+        Time(time::Options),
+        Stars,
         #[cfg(feature = "today")]
         Today,
     }
@@ -46,17 +53,52 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                jobs: parse_jobs(&mut args)?,
+                timeout: parse_timeout(&mut args)?,
+                reports: args.values_from_str("--report")?,
             },
             Some("time") => {
-                let all = args.contains("--all");
+                let run_all = args.contains("--all");
                 let store = args.contains("--store");
+                let jobs = parse_jobs(&mut args)?;
+                let timeout = parse_timeout(&mut args)?;
+                let reports = args.values_from_str("--report")?;
+                let fail_on_regression =
+                    args.opt_value_from_fn("--fail-on-regression", parse_percentage)?;
+                let compare = args.contains("--compare") || fail_on_regression.is_some();
+                let baseline = args.opt_value_from_str("--baseline")?;
+                let history = args.opt_value_from_str("--history")?;
+                let force = args.contains("--force");
+                let policy = args.opt_value_from_str("--policy")?;
+                let export_format = args.opt_value_from_str("--export")?;
+                let pinning = parse_pinning(&mut args)?;
+                let profiles = args
+                    .opt_value_from_fn("--profiles", parse_profiles)?
+                    .unwrap_or_default();
+                let (day, export_path) = parse_day_and_export_path(&mut args)?;
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
+                AppArguments::Time(time::Options {
+                    day,
+                    run_all,
                     store,
-                }
+                    jobs,
+                    timeout,
+                    reports,
+                    compare,
+                    fail_on_regression,
+                    baseline,
+                    history,
+                    force,
+                    export: export_format.map(|format| ExportTarget {
+                        format,
+                        path: export_path,
+                    }),
+                    policy,
+                    pinning,
+                    profiles,
+                })
             }
+            Some("stars") => AppArguments::Stars,
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -67,12 +109,16 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                overwrite_data: args.contains("--overwrite-data"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                time: args.contains("--time"),
+                pinning: parse_pinning(&mut args)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,27 +139,105 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--jobs")? {
+            Some(0) => Err("--jobs must be at least 1.".into()),
+            Some(jobs) => Ok(jobs),
+            None => Ok(1),
+        }
+    }
+
+    fn parse_pinning(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Pinning, Box<dyn std::error::Error>> {
+        Ok(Pinning {
+            cpu: args.opt_value_from_str("--pin-cpu")?,
+            high_priority: args.contains("--high-priority"),
+        })
+    }
+
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        Ok(args
+            .opt_value_from_str::<_, u64>("--timeout")?
+            .map(Duration::from_secs))
+    }
+
+    /// Parse the free arguments of `time`: an optional day and an optional export path, in any order.
+    /// `cargo time --export csv timings.csv` would otherwise be rejected as an invalid day.
+    fn parse_day_and_export_path(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(Option<Day>, Option<PathBuf>), Box<dyn std::error::Error>> {
+        let mut day = None;
+        let mut path = None;
+
+        while let Some(arg) = args.opt_free_from_str::<String>()? {
+            match arg.parse::<Day>() {
+                Ok(parsed) if day.is_none() => day = Some(parsed),
+                _ if path.is_none() && !arg.chars().all(|c| c.is_ascii_digit()) => {
+                    path = Some(PathBuf::from(arg));
+                }
+                _ => return Err(format!("unexpected argument \"{arg}\".").into()),
+            }
+        }
+
+        Ok((day, path))
+    }
+
+    /// Parse a percentage like `10%` or `10`.
+    fn parse_percentage(s: &str) -> Result<f64, String> {
+        let value: f64 = s
+            .strip_suffix('%')
+            .unwrap_or(s)
+            .trim()
+            .parse()
+            .map_err(|_| format!("expected a percentage like `10%`, got \"{s}\"."))?;
+
+        if value.is_finite() && value >= 0.0 {
+            Ok(value)
+        } else {
+            Err(format!("expected a positive percentage, got \"{s}\"."))
+        }
+    }
 }
 
 fn main() {
+    // NOTE: check the configuration up front, so invalid values are reported before any work is done.
+    if let Err(e) = config::load() {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                jobs,
+                timeout,
+                reports,
+            } => all::handle(release, jobs, timeout, &reports),
+            AppArguments::Time(options) => time::handle(&options),
+            AppArguments::Stars => stars::handle(),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                overwrite_data,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+                // NOTE: download first, so the template can refer to the puzzle title.
+                let downloaded = !download || download::try_handle(day);
+                scaffold::handle(day, overwrite, overwrite_data, template.as_deref());
+                if !downloaded {
+                    process::exit(1);
                 }
             }
             AppArguments::Solve {
@@ -121,13 +245,18 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                time,
+                pinning,
+            } => solve::handle(day, release, dhat, submit, time, pinning),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
+                        let downloaded = download::try_handle(day);
+                        scaffold::handle(day, false, false, None);
+                        if !downloaded {
+                            process::exit(1);
+                        }
                         read::handle(day)
                     }
                     None => {
@@ -0,0 +1,231 @@
+/// Module that reduces benchmark noise by pinning solutions to a single CPU core and raising their priority.
+/// Both are only supported on Linux, where the kernel functions are called directly to avoid a `libc` dependency.
+use std::{fs, io, process::Command};
+
+/// Environment variable that tells a solution binary which CPU core to pin itself to.
+pub(crate) const PIN_CPU_ENV: &str = "AOC_PIN_CPU";
+
+/// Niceness the benchmark process is set to with `--high-priority`. Lower values get more CPU time.
+const HIGH_PRIORITY_NICENESS: i32 = -10;
+
+/// How benched solutions are scheduled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pinning {
+    /// CPU core to run solutions on.
+    pub cpu: Option<usize>,
+    /// Whether the priority of solutions is raised.
+    pub high_priority: bool,
+}
+
+impl Pinning {
+    pub fn is_enabled(&self) -> bool {
+        self.cpu.is_some() || self.high_priority
+    }
+
+    /// Check the settings and apply them to the current process, so they are inherited by solutions started from it.
+    /// The priority is raised right away, the CPU core is applied by each solution on startup, see [`Pinning::pass_to`].
+    /// This way, compiling the solutions is not restricted to a single core.
+    pub fn prepare(&self) -> Result<(), String> {
+        if let Some(cpu) = self.cpu {
+            let allowed =
+                sys::allowed_cpus().map_err(|e| format!("Failed to read the CPU affinity: {e}"))?;
+
+            if !allowed.contains(&cpu) {
+                return Err(format!(
+                    "CPU {cpu} is not available, pick one of {}.",
+                    describe_cpus(&allowed)
+                ));
+            }
+
+            match governor(cpu) {
+                Some(governor) if governor != "performance" => eprintln!(
+                    "Warning: the frequency governor of CPU {cpu} is \"{governor}\". Timings will be noisier than with \"performance\"."
+                ),
+                _ => {}
+            }
+        }
+
+        if self.high_priority {
+            sys::set_niceness(HIGH_PRIORITY_NICENESS).map_err(|e| {
+                format!(
+                    "Failed to raise the priority: {e}. Raising the priority usually requires root."
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Tell a solution binary which CPU core to pin itself to.
+    pub(crate) fn pass_to(&self, cmd: &mut Command) {
+        if let Some(cpu) = self.cpu {
+            cmd.env(PIN_CPU_ENV, cpu.to_string());
+        }
+    }
+
+    /// Pin the calling thread to the configured CPU core, if any.
+    #[cfg(feature = "registry")]
+    pub(crate) fn pin_current_thread(&self) -> io::Result<()> {
+        self.cpu.map_or(Ok(()), pin_current_thread)
+    }
+
+    /// Describe the settings in a few words, e.g. for the readme.
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = vec![];
+
+        if let Some(cpu) = self.cpu {
+            parts.push(format!("pinned to CPU {cpu}"));
+        }
+
+        if self.high_priority {
+            parts.push("high priority".into());
+        }
+
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// Restrict the calling thread, and threads and processes started from it, to a single CPU core.
+pub(crate) fn pin_current_thread(cpu: usize) -> io::Result<()> {
+    sys::pin_current_thread(cpu)
+}
+
+/// Frequency governor of a CPU core, e.g. `performance` or `powersave`. `None` if it is not exposed by the kernel.
+pub fn governor(cpu: usize) -> Option<String> {
+    fs::read_to_string(format!(
+        "/sys/devices/system/cpu/cpu{cpu}/cpufreq/scaling_governor"
+    ))
+    .ok()
+    .map(|x| x.trim().to_string())
+    .filter(|x| !x.is_empty())
+}
+
+/// Describe a list of CPU cores as ranges, e.g. `0-3, 6`.
+fn describe_cpus(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|&(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::io;
+
+    /// Number of CPUs a `cpu_set_t` holds.
+    const MAX_CPUS: usize = 1024;
+    const PRIO_PROCESS: i32 = 0;
+
+    type CpuSet = [u64; MAX_CPUS / 64];
+
+    unsafe extern "C" {
+        fn sched_setaffinity(pid: i32, cpusetsize: usize, mask: *const CpuSet) -> i32;
+        fn sched_getaffinity(pid: i32, cpusetsize: usize, mask: *mut CpuSet) -> i32;
+        fn setpriority(which: i32, who: u32, prio: i32) -> i32;
+    }
+
+    pub fn pin_current_thread(cpu: usize) -> io::Result<()> {
+        if cpu >= MAX_CPUS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("CPU index must be below {MAX_CPUS}"),
+            ));
+        }
+
+        let mut mask: CpuSet = [0; MAX_CPUS / 64];
+        mask[cpu / 64] |= 1 << (cpu % 64);
+
+        // SAFETY: `mask` is a valid `cpu_set_t` of the given size, pid 0 refers to the calling thread.
+        match unsafe { sched_setaffinity(0, size_of::<CpuSet>(), &raw const mask) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+
+    pub fn allowed_cpus() -> io::Result<Vec<usize>> {
+        let mut mask: CpuSet = [0; MAX_CPUS / 64];
+
+        // SAFETY: `mask` is a valid, writable `cpu_set_t` of the given size.
+        if unsafe { sched_getaffinity(0, size_of::<CpuSet>(), &raw mut mask) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok((0..MAX_CPUS)
+            .filter(|cpu| mask[cpu / 64] & (1 << (cpu % 64)) != 0)
+            .collect())
+    }
+
+    pub fn set_niceness(niceness: i32) -> io::Result<()> {
+        // SAFETY: plain syscall without pointers, who 0 refers to the calling process.
+        match unsafe { setpriority(PRIO_PROCESS, 0, niceness) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use std::io;
+
+    fn unsupported() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "only supported on Linux".to_string(),
+        )
+    }
+
+    pub fn pin_current_thread(_cpu: usize) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn allowed_cpus() -> io::Result<Vec<usize>> {
+        Err(unsupported())
+    }
+
+    pub fn set_niceness(_niceness: i32) -> io::Result<()> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Pinning, describe_cpus};
+
+    #[test]
+    fn describes_cpu_ranges() {
+        assert_eq!(describe_cpus(&[0, 1, 2, 3, 6]), "0-3, 6");
+        assert_eq!(describe_cpus(&[5]), "5");
+        assert_eq!(describe_cpus(&[]), "");
+    }
+
+    #[test]
+    fn describes_pinning() {
+        assert_eq!(Pinning::default().describe(), None);
+        assert_eq!(
+            Pinning {
+                cpu: Some(3),
+                high_priority: true
+            }
+            .describe()
+            .as_deref(),
+            Some("pinned to CPU 3, high priority")
+        );
+    }
+}
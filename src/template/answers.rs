@@ -0,0 +1,116 @@
+/// Module that reads known answers and titles from downloaded puzzle descriptions.
+/// Once a part is solved, the puzzle page contains a line like "Your puzzle answer was `42`.".
+use std::fs;
+
+use crate::template::{Day, aoc_cli};
+
+/// The accepted answers for a day, if known.
+#[derive(Clone, Debug, Default)]
+pub struct KnownAnswers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl KnownAnswers {
+    /// Read the known answers for a day from its puzzle description. Returns no answers if the puzzle was not downloaded.
+    pub fn read(day: Day) -> Self {
+        fs::read_to_string(aoc_cli::get_puzzle_path(day))
+            .map(|puzzle| Self::parse(&puzzle))
+            .unwrap_or_default()
+    }
+
+    fn parse(puzzle: &str) -> Self {
+        let mut answers = puzzle.lines().filter_map(|line| {
+            let answer = line
+                .split_once("Your puzzle answer was")?
+                .1
+                .trim()
+                .trim_end_matches('.')
+                .trim_matches(|c| c == '`' || c == '*')
+                .trim();
+
+            (!answer.is_empty()).then(|| answer.to_string())
+        });
+
+        KnownAnswers {
+            part_1: answers.next(),
+            part_2: answers.next(),
+        }
+    }
+
+    pub fn get(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Read the title of a puzzle from its description, e.g. "Historian Hysteria".
+pub fn read_title(day: Day) -> Option<String> {
+    fs::read_to_string(aoc_cli::get_puzzle_path(day))
+        .ok()
+        .and_then(|puzzle| parse_title(&puzzle))
+}
+
+fn parse_title(puzzle: &str) -> Option<String> {
+    // NOTE: the heading looks like `## \--- Day 1: Historian Hysteria ---`.
+    let title = puzzle
+        .lines()
+        .find_map(|line| line.split_once("--- Day "))?
+        .1
+        .split_once(": ")?
+        .1
+        .trim_end()
+        .trim_end_matches("---")
+        .trim();
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{KnownAnswers, parse_title};
+
+    #[test]
+    fn parses_solved_puzzles() {
+        let puzzle = [
+            "## \\--- Day 1: Historian Hysteria ---",
+            "Your puzzle answer was `1830467`.",
+            "## \\--- Part Two ---",
+            "Your puzzle answer was `26674158`.",
+            "Both parts of this puzzle are complete! They provide two gold stars: \\*\\*",
+        ]
+        .join("\n");
+
+        let answers = KnownAnswers::parse(&puzzle);
+        assert_eq!(answers.get(1), Some(&"1830467".to_string()));
+        assert_eq!(answers.get(2), Some(&"26674158".to_string()));
+    }
+
+    #[test]
+    fn handles_partially_solved_puzzles() {
+        let answers = KnownAnswers::parse(
+            "Your puzzle answer was `abc,def`.\n\nThe first half of this puzzle is complete!",
+        );
+        assert_eq!(answers.get(1), Some(&"abc,def".to_string()));
+        assert_eq!(answers.get(2), None);
+    }
+
+    #[test]
+    fn handles_unsolved_puzzles() {
+        let answers = KnownAnswers::parse("## \\--- Day 1: Historian Hysteria ---");
+        assert!(answers.part_1.is_none());
+        assert!(answers.part_2.is_none());
+    }
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(
+            parse_title("## \\--- Day 1: Historian Hysteria ---\n\nThe Chief Historian..."),
+            Some("Historian Hysteria".into())
+        );
+        assert_eq!(parse_title("# Not a puzzle"), None);
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{Day, config};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -80,24 +80,26 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+pub(crate) fn get_input_path(day: Day) -> String {
+    config::get().data_path("inputs", &format!("{day}.txt"))
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+pub(crate) fn get_puzzle_path(day: Day) -> String {
+    config::get().data_path("puzzles", &format!("{day}.md"))
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+pub(crate) fn get_year() -> Option<u16> {
+    config::get().year
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
+    if let Some(session_file) = &config::get().session_file {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_file.clone());
+    }
+
     if let Some(year) = get_year() {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
@@ -0,0 +1,212 @@
+/// Module that renders stored timings as an SVG bar chart, so they can be scanned at a glance in the readme.
+/// The chart is written by hand to avoid pulling in a plotting dependency.
+use std::{fmt::Write, fs, io, path::Path};
+
+use crate::template::timings::{Timings, format_nanos};
+
+/// File name of the chart in the data directory, unless `chart` is set in the `[readme]` table of `aoc.toml`.
+pub(crate) static DEFAULT_CHART_FILE: &str = "benchmarks.svg";
+
+const PART_COLORS: [&str; 2] = ["#4c78a8", "#f2a93b"];
+
+const LABEL_WIDTH: f64 = 64.0;
+const PLOT_WIDTH: f64 = 560.0;
+const VALUE_WIDTH: f64 = 64.0;
+const BAR_HEIGHT: f64 = 10.0;
+const ROW_GAP: f64 = 8.0;
+const HEADER_HEIGHT: f64 = 40.0;
+const AXIS_HEIGHT: f64 = 28.0;
+
+/// Render the chart and write it to `path`, creating parent directories if needed.
+pub fn store(timings: &Timings, path: &str) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, render(timings))
+}
+
+/// Render one row per day with a bar per part. Durations span several orders of magnitude, so the x-axis is
+/// logarithmic with a tick per power of ten.
+pub fn render(timings: &Timings) -> String {
+    let nanos: Vec<f64> = timings
+        .data
+        .iter()
+        .flat_map(|t| [t.part_nanos(1), t.part_nanos(2)])
+        .flatten()
+        .map(|x| x.max(1.0))
+        .collect();
+
+    let min = nanos.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = nanos.iter().copied().fold(1.0, f64::max);
+
+    #[allow(clippy::cast_possible_truncation)]
+    let (lo, hi) = if nanos.is_empty() {
+        (0, 1)
+    } else {
+        let lo = min.log10().floor() as i32;
+        (lo, (max.log10().ceil() as i32).max(lo + 1))
+    };
+
+    let scale =
+        |nanos: f64| (nanos.max(1.0).log10() - f64::from(lo)) / f64::from(hi - lo) * PLOT_WIDTH;
+
+    let row_height = 2.0 * BAR_HEIGHT + ROW_GAP;
+    #[allow(clippy::cast_precision_loss)]
+    let plot_height = timings.data.len() as f64 * row_height;
+    let width = LABEL_WIDTH + PLOT_WIDTH + VALUE_WIDTH;
+    let height = HEADER_HEIGHT + plot_height + AXIS_HEIGHT;
+
+    let mut svg = String::new();
+
+    // NOTE: writing to a `String` can not fail.
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{LABEL_WIDTH}" y="16" font-weight="bold">Benchmarks (log scale)</text>"#
+    );
+
+    for (i, color) in PART_COLORS.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = LABEL_WIDTH + 200.0 + i as f64 * 70.0;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x}" y="7" width="10" height="10" fill="{color}"/><text x="{}" y="16">Part {}</text>"#,
+            x + 14.0,
+            i + 1
+        );
+    }
+
+    for exponent in lo..=hi {
+        let x = LABEL_WIDTH + scale(10_f64.powi(exponent));
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{x:.1}" y1="{HEADER_HEIGHT}" x2="{x:.1}" y2="{}" stroke="#dddddd"/>"##,
+            HEADER_HEIGHT + plot_height
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x:.1}" y="{}" text-anchor="middle" font-size="10">{}</text>"#,
+            HEADER_HEIGHT + plot_height + 16.0,
+            format_tick(exponent)
+        );
+    }
+
+    for (row, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = HEADER_HEIGHT + row as f64 * row_height + ROW_GAP / 2.0;
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="end">Day {}</text>"#,
+            LABEL_WIDTH - 8.0,
+            y + BAR_HEIGHT + 4.0,
+            timing.day
+        );
+
+        for (i, color) in PART_COLORS.iter().enumerate() {
+            #[allow(clippy::cast_possible_truncation)]
+            let Some(nanos) = timing.part_nanos(i as u8 + 1) else {
+                continue;
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let bar_y = y + i as f64 * BAR_HEIGHT;
+            let bar_width = scale(nanos).max(1.0);
+            let label = format_nanos(nanos);
+
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{LABEL_WIDTH}" y="{bar_y}" width="{bar_width:.1}" height="{BAR_HEIGHT}" fill="{color}"><title>Day {} part {}: {label}</title></rect>"#,
+                timing.day,
+                i + 1
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{}" font-size="9">{label}</text>"#,
+                LABEL_WIDTH + bar_width + 4.0,
+                bar_y + BAR_HEIGHT - 1.0
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Label a power of ten in nanoseconds, e.g. `100ns`, `1µs` or `10s`.
+fn format_tick(exponent: i32) -> String {
+    let (unit, offset) = match exponent {
+        ..3 => ("ns", 0),
+        3..6 => ("µs", 3),
+        6..9 => ("ms", 6),
+        _ => ("s", 9),
+    };
+
+    format!(
+        "{}{unit}",
+        10_u64.pow((exponent - offset).max(0).unsigned_abs())
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_tick, render};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    #[test]
+    fn formats_ticks() {
+        assert_eq!(format_tick(0), "1ns");
+        assert_eq!(format_tick(2), "100ns");
+        assert_eq!(format_tick(3), "1µs");
+        assert_eq!(format_tick(7), "10ms");
+        assert_eq!(format_tick(10), "10s");
+    }
+
+    #[test]
+    fn renders_a_bar_per_part() {
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some(PartTiming::from_nanos(1_000_f64)),
+                    part_2: Some(PartTiming::from_nanos(1_000_000_f64)),
+                    parse: None,
+                    total_nanos: 1_001_000_f64,
+                    peak_memory_bytes: None,
+                    history: vec![],
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some(PartTiming::from_nanos(100_f64)),
+                    part_2: None,
+                    parse: None,
+                    total_nanos: 100_f64,
+                    peak_memory_bytes: None,
+                    history: vec![],
+                },
+            ],
+            ..Timings::default()
+        };
+
+        let svg = render(&timings);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains("<title>Day 01 part 2: 1.0ms</title>"));
+        // axis spans 100ns to 1ms.
+        assert!(svg.contains(">100ns</text>"));
+        assert!(svg.contains(">1ms</text>"));
+        assert!(!svg.contains(">10ns</text>"));
+    }
+}
@@ -1,5 +1,35 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    Pinning, ReportTarget, all_days,
+    report::write_reports,
+    run_multi::{RunOptions, run_multi},
+};
+
+pub fn handle(is_release: bool, jobs: usize, timeout: Option<Duration>, reports: &[ReportTarget]) {
+    let options = RunOptions {
+        is_release,
+        is_timed: false,
+        jobs,
+        timeout,
+        pinning: Pinning::default(),
+        profile: None,
+    };
+
+    match run_multi(&all_days().collect(), &options) {
+        Ok(outcome) => {
+            if let Err(e) = write_reports(&outcome, reports) {
+                eprintln!("Failed to write report: {e}");
+                process::exit(1);
+            }
+
+            if !outcome.is_success() {
+                process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
 }
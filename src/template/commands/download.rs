@@ -1,16 +1,27 @@
-use crate::template::{Day, aoc_cli};
+use crate::template::{Day, aoc_cli, examples};
 use std::process;
 
 pub fn handle(day: Day) {
+    if !try_handle(day) {
+        process::exit(1);
+    }
+}
+
+/// Download the input and puzzle of a day and write the examples found in the puzzle, printing any errors.
+/// Returns whether the download succeeded.
+pub fn try_handle(day: Day) -> bool {
     if aoc_cli::check().is_err() {
         eprintln!(
             "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
         );
-        process::exit(1);
+        return false;
     }
 
     if let Err(e) = aoc_cli::download(day) {
         eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+        return false;
+    }
+
+    examples::update(day);
+    true
 }
@@ -1,79 +1,138 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::templates::{self, TemplateContext};
+use crate::template::{Day, aoc_cli, config, examples};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
-
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+/// Create a file, truncating it if it exists and `overwrite` is set.
+/// Returns `None` if the file exists and is kept.
+fn safe_create_file(path: &str, overwrite: bool) -> Result<Option<File>, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
     } else {
         file.create_new(true);
     }
-    file.truncate(true).write(true).open(path)
-}
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+    match file.truncate(true).write(true).open(path) {
+        Ok(file) => Ok(Some(file)),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(None),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create an empty data file unless it exists. Inputs and examples are never reset without `--overwrite-data`.
+fn create_data_file(path: &str, kind: &str, overwrite: bool) {
+    // NOTE: the data directory is configurable, so its folders may not exist yet.
+    if let Some(parent) = Path::new(path).parent() {
+        let _ = fs::create_dir_all(parent);
+    }
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    match safe_create_file(path, overwrite) {
+        Ok(Some(_)) => {
+            println!("Created empty {kind} file \"{path}\"");
         }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+        Ok(None) => {
+            println!(
+                "Skipped {kind} file \"{path}\", it already exists. Pass `--overwrite-data` to reset it."
+            );
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to create {kind} file: {e}");
             process::exit(1);
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+pub fn handle(day: Day, overwrite: bool, overwrite_data: bool, template: Option<&str>) {
+    let template_name = template.map_or_else(templates::default_name, str::to_string);
+
+    let module_template = match templates::load(&template_name) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to load template: {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    let input_path = aoc_cli::get_input_path(day);
+    let example_path = config::get().data_path("examples", &format!("{day}.txt"));
+    let module_path = format!("src/bin/{day}.rs");
+
+    match safe_create_file(&module_path, overwrite) {
+        Ok(Some(mut file)) => {
+            match file.write_all(
+                TemplateContext::collect(day)
+                    .render(&module_template)
+                    .as_bytes(),
+            ) {
+                Ok(()) => {
+                    println!(
+                        "Created module file \"{}\" from the {template_name} template",
+                        &module_path
+                    );
+                }
+                Err(e) => {
+                    eprintln!("Failed to write module contents: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+        Ok(None) => {
+            println!(
+                "Skipped module file \"{module_path}\", it already exists. Pass `--overwrite` to reset it."
+            );
         }
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to create module file: {e}");
             process::exit(1);
         }
     }
 
+    create_data_file(&input_path, "input", overwrite_data);
+    create_data_file(&example_path, "example", overwrite_data);
+
+    // NOTE: fills in the examples of a downloaded puzzle, does nothing otherwise.
+    examples::update(day);
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::create_data_file;
+    use std::{env, fs, process};
+
+    #[test]
+    fn keeps_data_files_unless_overwritten() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        let input = dir.join("inputs/01.txt").display().to_string();
+        let example = dir.join("examples/01.txt").display().to_string();
+
+        // NOTE: missing files and their folders are created.
+        create_data_file(&input, "input", false);
+        create_data_file(&example, "example", false);
+        assert_eq!(fs::read_to_string(&input).unwrap(), "");
+        assert_eq!(fs::read_to_string(&example).unwrap(), "");
+
+        fs::write(&input, "puzzle input").unwrap();
+        fs::write(&example, "example").unwrap();
+
+        create_data_file(&input, "input", false);
+        create_data_file(&example, "example", false);
+        assert_eq!(fs::read_to_string(&input).unwrap(), "puzzle input");
+        assert_eq!(fs::read_to_string(&example).unwrap(), "example");
+
+        create_data_file(&input, "input", true);
+        create_data_file(&example, "example", true);
+        assert_eq!(fs::read_to_string(&input).unwrap(), "");
+        assert_eq!(fs::read_to_string(&example).unwrap(), "");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,8 +1,20 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{Day, Pinning};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    time: bool,
+    pinning: Pinning,
+) {
+    if let Err(e) = pinning.prepare() {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -18,12 +30,19 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    if time {
+        cmd_args.push("--time".to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    let mut cmd = Command::new("cargo");
+    pinning.pass_to(&mut cmd);
+
+    let mut cmd = cmd
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -0,0 +1,18 @@
+use std::process;
+
+use crate::template::{readme_stars, timings::Timings};
+
+pub fn handle() {
+    let timings = Timings::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Ignoring stored timings: {e}");
+        Timings::default()
+    });
+
+    match readme_stars::update(&timings) {
+        Ok(stars) => println!("Updated readme with {stars} ⭐."),
+        Err(e) => {
+            eprintln!("Failed to update readme: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,15 +1,106 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process, time::Duration};
 
-use crate::template::run_multi::run_multi;
+use crate::template::compare;
+use crate::template::export::export;
+use crate::template::history::{RunContext, print_history};
+use crate::template::report::write_reports;
+use crate::template::run_multi::{Outcome, RunOptions, run_multi, run_multi_in};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::worktree::Worktree;
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, BuildProfile, Day, ExportTarget, MergePolicy, Pinning, ReportTarget,
+    all_days, ansi, profiles, readme_benchmarks,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Options of the `time` command.
+pub struct Options {
+    pub day: Option<Day>,
+    pub run_all: bool,
+    pub store: bool,
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+    pub reports: Vec<ReportTarget>,
+    pub compare: bool,
+    pub fail_on_regression: Option<f64>,
+    /// A git revision to compare the working tree against.
+    pub baseline: Option<String>,
+    /// Show the stored history of a day instead of benching.
+    pub history: Option<Day>,
+    /// Continue with empty timings if the stored ones can not be read.
+    pub force: bool,
+    /// Export the stored timings merged with the fresh ones.
+    pub export: Option<ExportTarget>,
+    /// How fresh timings are merged into stored ones. Defaults to the policy recorded in the stored timings.
+    pub policy: Option<MergePolicy>,
+    /// CPU core and priority to bench solutions with.
+    pub pinning: Pinning,
+    /// Bench every day under each of these profiles and print a comparison instead of storing timings.
+    pub profiles: Vec<BuildProfile>,
+}
+
+pub fn handle(options: &Options) {
+    let stored_timings = match Timings::read_from_file() {
+        Ok(timings) => timings,
+        Err(e) if options.force && options.history.is_none() => {
+            eprintln!("Warning: {e}\nContinuing without stored timings because of `--force`.");
+            if options.store {
+                eprintln!(
+                    "The file will be overwritten, its contents are kept in a copy next to it."
+                );
+            }
+            Timings::default()
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            eprintln!("Fix the file or pass `--force` to ignore its contents.");
+            process::exit(1);
+        }
+    };
+
+    if let Some(day) = options.history {
+        print_history(day, &stored_timings);
+        return;
+    }
+
+    if !options.profiles.is_empty()
+        && (options.store
+            || options.compare
+            || options.baseline.is_some()
+            || options.export.is_some())
+    {
+        eprintln!(
+            "Error: `--profiles` can not be combined with `--store`, `--compare`, `--baseline` or `--export`."
+        );
+        process::exit(1);
+    }
+
+    if options.jobs > 1 && options.pinning.cpu.is_some() {
+        eprintln!(
+            "Error: `--pin-cpu` can not be combined with `--jobs`, all days would share one core."
+        );
+        process::exit(1);
+    }
+
+    if let Err(e) = options.pinning.prepare() {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+
+    if options.jobs > 1 {
+        eprintln!(
+            "Warning: benching {} days in parallel. Timings will be noisier than in a sequential run.",
+            options.jobs
+        );
+    }
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = options.day.map_or_else(
         || {
-            if run_all {
+            // NOTE: comparisons need fresh timings for days that are already benched.
+            if options.run_all
+                || options.compare
+                || options.baseline.is_some()
+                || !options.profiles.is_empty()
+            {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,20 +112,175 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run_options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        jobs: options.jobs,
+        timeout: options.timeout,
+        pinning: options.pinning,
+        profile: None,
+    };
+
+    if !options.profiles.is_empty() {
+        if !run_profiles(&options.profiles, &days_to_run, &run_options) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let baseline = options.baseline.as_deref().map(|rev| {
+        let heading = format!("Baseline ({rev})");
+        println!("{}{heading}{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
+        println!("{}\n", "=".repeat(heading.len()));
+
+        let Some(outcome) = run_baseline(rev, &days_to_run, &run_options) else {
+            process::exit(1);
+        };
+
+        println!("\n{}Working tree{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
+        println!("============\n");
+        (rev, outcome.timings)
+    });
+
+    let outcome = match run_multi(&days_to_run, &run_options) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = write_reports(&outcome, &options.reports) {
+        eprintln!("Failed to write report: {e}");
+        process::exit(1);
+    }
+
+    let mut has_regressions = false;
+
+    let comparison = match &baseline {
+        Some((rev, timings)) => Some((*rev, timings, "Working tree")),
+        None => options
+            .compare
+            .then_some(("Stored", &stored_timings, "Current")),
+    };
+
+    if let Some((baseline_label, baseline_timings, current_label)) = comparison {
+        let comparisons = compare::compare(baseline_timings, &outcome.timings);
+        compare::print_table(&comparisons, baseline_label, current_label);
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        if let Some(threshold) = options.fail_on_regression {
+            let regressions = comparisons
+                .iter()
+                .filter(|c| c.is_regression(threshold))
+                .count();
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+            if regressions > 0 {
+                has_regressions = true;
+                eprintln!("\n{regressions} part(s) got slower by more than {threshold}%.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        }
+    }
+
+    if options.store || options.export.is_some() {
+        let context = RunContext::collect(&run_options);
+        let policy = options.policy.unwrap_or(stored_timings.policy);
+        let merged_timings =
+            stored_timings.merge(&outcome.timings.with_history_entry(&context), policy);
+
+        let exported = options
+            .export
+            .as_ref()
+            .map_or(Ok(()), |target| export(&merged_timings, target));
+
+        if let Err(e) = exported {
+            eprintln!("Failed to export timings: {e}");
+            process::exit(1);
+        }
+
+        if options.store {
+            match merged_timings.store_file() {
+                Ok(Some(kept)) => eprintln!("Kept the unreadable timings in {kept}."),
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("Failed to store timings: {e}");
+                    process::exit(1);
+                }
             }
+
+            println!();
+            match readme_benchmarks::update(merged_timings) {
+                Ok(()) => {
+                    println!("Stored updated benchmarks.");
+                }
+                Err(e) => {
+                    eprintln!("Failed to store updated benchmarks: {e}");
+                }
+            }
+        }
+    }
+
+    if !outcome.is_success() || has_regressions {
+        process::exit(1);
+    }
+}
+
+/// Bench the given days under each profile and print a comparison matrix.
+/// Returns `false` if a profile could not be run or a day failed.
+fn run_profiles(
+    build_profiles: &[BuildProfile],
+    days: &HashSet<Day>,
+    options: &RunOptions,
+) -> bool {
+    let mut timings = vec![];
+    let mut is_success = true;
+
+    for (i, profile) in build_profiles.iter().enumerate() {
+        let heading = format!("Profile {}", profile.name);
+        println!(
+            "{}{}{heading}{}",
+            if i > 0 { "\n" } else { "" },
+            ansi(ANSI_BOLD),
+            ansi(ANSI_RESET)
+        );
+        println!("{}\n", "=".repeat(heading.len()));
+
+        let profile_options = RunOptions {
+            profile: Some(profile.clone()),
+            ..*options
+        };
+
+        match run_multi(days, &profile_options) {
+            Ok(outcome) => {
+                is_success &= outcome.is_success();
+                timings.push(outcome.timings);
+            }
+            Err(e) => {
+                eprintln!("Error: {e}");
+                return false;
+            }
+        }
+    }
+
+    profiles::print_matrix(build_profiles, &timings);
+    is_success
+}
+
+/// Bench the given days at `rev`, checked out into a temporary git worktree.
+/// Returns `None` if the revision could not be checked out or run.
+fn run_baseline(rev: &str, days: &HashSet<Day>, options: &RunOptions) -> Option<Outcome> {
+    let worktree = match Worktree::checkout(rev) {
+        Ok(worktree) => worktree,
+        Err(e) => {
+            eprintln!("Failed to check out \"{rev}\": {e}");
+            return None;
+        }
+    };
+
+    match run_multi_in(worktree.path(), days, options) {
+        Ok(outcome) => Some(outcome),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            None
         }
     }
 }
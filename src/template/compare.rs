@@ -0,0 +1,151 @@
+/// Module that compares two sets of timings part by part, e.g. stored benchmarks against a fresh run.
+use crate::template::timings::{Timings, format_nanos};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, Day, ansi};
+
+/// The timing of a single part in a baseline and a current set of timings.
+#[derive(Clone, Debug)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: Option<f64>,
+    pub current_nanos: f64,
+}
+
+impl PartComparison {
+    /// Relative change in percent. Positive values mean that the part got slower.
+    pub fn change_percent(&self) -> Option<f64> {
+        let baseline = self.baseline_nanos.filter(|x| *x > 0.0)?;
+        Some((self.current_nanos - baseline) / baseline * 100.0)
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent()
+            .is_some_and(|change| change > threshold)
+    }
+}
+
+/// Compare every timed part in `current` against the same part in `baseline`.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartComparison> {
+    current
+        .data
+        .iter()
+        .flat_map(|timing| {
+            (1..=2).filter_map(move |part| {
+                Some(PartComparison {
+                    day: timing.day,
+                    part,
+                    baseline_nanos: baseline.get(timing.day).and_then(|t| t.part_nanos(part)),
+                    current_nanos: timing.part_nanos(part)?,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Print a table of comparisons with coloured deltas.
+pub fn print_table(comparisons: &[PartComparison], baseline_label: &str, current_label: &str) {
+    println!("\n{}Comparison{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    println!("----------");
+
+    if comparisons.is_empty() {
+        println!("No timings to compare.");
+        return;
+    }
+
+    let baseline_width = baseline_label.len().max(12);
+    let current_width = current_label.len().max(12);
+
+    println!(
+        "{:<4} {:<6} {:>baseline_width$} {:>current_width$}  Delta",
+        "Day", "Part", baseline_label, current_label
+    );
+
+    for comparison in comparisons {
+        let baseline = comparison
+            .baseline_nanos
+            .map_or_else(|| "-".into(), format_nanos);
+
+        println!(
+            "{:<4} {:<6} {:>baseline_width$} {:>current_width$}  {}",
+            comparison.day.to_string(),
+            comparison.part,
+            baseline,
+            format_nanos(comparison.current_nanos),
+            format_delta(comparison)
+        );
+    }
+}
+
+fn format_delta(comparison: &PartComparison) -> String {
+    let (Some(baseline), Some(change)) = (comparison.baseline_nanos, comparison.change_percent())
+    else {
+        return "-".into();
+    };
+
+    let delta = comparison.current_nanos - baseline;
+    let sign = if delta < 0.0 { "-" } else { "+" };
+    let color = ansi(if delta < 0.0 { ANSI_GREEN } else { ANSI_RED });
+
+    format!(
+        "{color}{sign}{} ({change:+.1}%){}",
+        format_nanos(delta.abs()),
+        ansi(ANSI_RESET)
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::compare;
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings, parse_nanos},
+    };
+
+    fn timing(day: crate::template::Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: part_1.and_then(parse_nanos).map(PartTiming::from_nanos),
+            part_2: part_2.and_then(parse_nanos).map(PartTiming::from_nanos),
+            parse: None,
+            total_nanos: 0_f64,
+            peak_memory_bytes: None,
+            history: vec![],
+        }
+    }
+
+    #[test]
+    fn compares_matching_parts() {
+        let baseline = Timings {
+            data: vec![timing(day!(1), Some("10.0ms"), Some("20.0ms"))],
+            ..Timings::default()
+        };
+        let current = Timings {
+            data: vec![timing(day!(1), Some("11.0ms"), Some("10.0ms"))],
+            ..Timings::default()
+        };
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 2);
+        assert!((comparisons[0].change_percent().unwrap() - 10.0).abs() < 1e-9);
+        assert!((comparisons[1].change_percent().unwrap() + 50.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(5.0));
+        assert!(!comparisons[0].is_regression(10.0));
+        assert!(!comparisons[1].is_regression(0.0));
+    }
+
+    #[test]
+    fn handles_missing_baseline() {
+        let baseline = Timings::default();
+        let current = Timings {
+            data: vec![timing(day!(2), Some("1.0µs"), None)],
+            ..Timings::default()
+        };
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, 1);
+        assert!(comparisons[0].change_percent().is_none());
+        assert!(!comparisons[0].is_regression(0.0));
+    }
+}
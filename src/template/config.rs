@@ -0,0 +1,761 @@
+/// Module that reads the settings of the project from `aoc.toml`.
+/// The file is optional and only supports the subset of TOML it needs: `[table]` headers, bare keys, single-line
+/// basic strings, integers and booleans. Other TOML, e.g. arrays, inline tables or dotted keys, is reported as an
+/// error instead of being misread. Unknown keys and invalid values are reported as errors too, so typos do not go unnoticed.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::template::{
+    benchmark_chart::DEFAULT_CHART_FILE,
+    readme_benchmarks::{self, Column, DEFAULT_COLUMNS, Sort},
+};
+
+pub static CONFIG_PATH: &str = "aoc.toml";
+
+/// Environment variable that overrides `year`.
+static YEAR_ENV: &str = "AOC_YEAR";
+/// Environment variable that overrides `template`.
+static TEMPLATE_ENV: &str = "AOC_TEMPLATE";
+/// Environment variable that overrides `color`. Set by `all` and `time` for the solutions they run.
+pub(crate) static COLOR_ENV: &str = "AOC_COLOR";
+/// Environment variable that disables colors, see <https://no-color.org>.
+static NO_COLOR_ENV: &str = "NO_COLOR";
+
+/// The first year of Advent of Code.
+const FIRST_YEAR: u16 = 2015;
+
+static CONFIG: OnceLock<Result<Config, Error>> = OnceLock::new();
+/// Defaults [`get`] falls back to if the configuration is invalid.
+static FALLBACK: OnceLock<Config> = OnceLock::new();
+
+/// Settings of the project.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Year of the puzzles. Passed to aoc-cli, which uses the current year otherwise.
+    pub year: Option<u16>,
+    /// Template `scaffold` uses without `--template`.
+    pub template: Option<String>,
+    pub color: ColorChoice,
+    /// Directory holding inputs, examples, puzzles and timings.
+    pub data_dir: String,
+    /// Markdown file the stars and benchmarks are written to.
+    pub readme: String,
+    /// Session cookie file passed to aoc-cli. aoc-cli looks in its default locations otherwise.
+    pub session_file: Option<String>,
+    pub bench: BenchConfig,
+    pub table: TableLayout,
+}
+
+/// Layout of the benchmark table in the readme, set in the `[readme]` table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableLayout {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Name of the table, for files with several tables.
+    pub name: Option<String>,
+    /// Path of the benchmark chart, `None` if it is disabled. Defaults to `benchmarks.svg` in the data directory.
+    pub chart: Option<String>,
+}
+
+/// How long a part is benched with `--time`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate time spent benching a part. Slow parts take longer to reach `min_samples`.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+/// Whether output is colored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color output written to a terminal, unless `NO_COLOR` is set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let data_dir = "data";
+
+        Config {
+            year: None,
+            template: None,
+            color: ColorChoice::Auto,
+            data_dir: data_dir.into(),
+            readme: "README.md".into(),
+            session_file: None,
+            bench: BenchConfig {
+                budget: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10_000,
+            },
+            table: TableLayout {
+                columns: DEFAULT_COLUMNS.to_vec(),
+                sort: Sort::Day,
+                name: None,
+                chart: Some(default_chart_path(data_dir)),
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    /// The file is not valid in the supported subset of TOML.
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    /// A setting has a value that is not allowed. `line` is `None` for values from the environment.
+    Invalid {
+        key: String,
+        line: Option<usize>,
+        message: String,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "could not read {CONFIG_PATH}: {e}"),
+            Error::Syntax { line, message } => write!(f, "{CONFIG_PATH}:{line}: {message}"),
+            Error::Invalid {
+                key,
+                line: Some(line),
+                message,
+            } => write!(
+                f,
+                "{CONFIG_PATH}:{line}: invalid value for `{key}`: {message}"
+            ),
+            Error::Invalid {
+                key,
+                line: None,
+                message,
+            } => write!(f, "invalid value for `{key}`: {message}"),
+        }
+    }
+}
+
+impl Config {
+    /// Whether output should be colored.
+    pub fn use_color(&self) -> bool {
+        match self.color {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => env::var_os(NO_COLOR_ENV).is_none() && io::stdout().is_terminal(),
+        }
+    }
+
+    /// Path of a file in the data directory, e.g. `data/inputs/01.txt`.
+    pub fn data_path(&self, folder: &str, file: &str) -> String {
+        Path::new(&self.data_dir)
+            .join(folder)
+            .join(file)
+            .display()
+            .to_string()
+    }
+
+    /// Parse the contents of `aoc.toml`. Settings that are not present keep their defaults.
+    fn parse(source: &str) -> Result<Self, Error> {
+        let mut config = Config::default();
+        // NOTE: the default chart path depends on `paths.data`, which may come later in the file.
+        let mut chart = None;
+
+        for entry in parse_entries(source)? {
+            match entry.key.as_str() {
+                "year" => {
+                    config.year = Some(parse_year(entry.integer()?).map_err(|e| entry.invalid(e))?)
+                }
+                "template" => config.template = Some(entry.non_empty_string()?),
+                "color" => config.color = entry.string()?.parse().map_err(|e| entry.invalid(e))?,
+                "paths.data" => config.data_dir = entry.non_empty_string()?,
+                "paths.readme" => config.readme = entry.non_empty_string()?,
+                "paths.session_file" => {
+                    config.session_file = Some(expand_home(&entry.non_empty_string()?))
+                }
+                "bench.budget_ms" => {
+                    config.bench.budget = Duration::from_millis(entry.positive_integer()?);
+                }
+                "bench.min_samples" => config.bench.min_samples = entry.positive_integer()?.into(),
+                "bench.max_samples" => config.bench.max_samples = entry.positive_integer()?.into(),
+                "readme.columns" => {
+                    config.table.columns =
+                        readme_benchmarks::parse_columns(&entry.non_empty_string()?)
+                            .map_err(|e| entry.invalid(e))?;
+                }
+                "readme.sort" => {
+                    config.table.sort = entry.string()?.parse().map_err(|e| entry.invalid(e))?
+                }
+                "readme.table" => config.table.name = Some(entry.non_empty_string()?),
+                // NOTE: an empty path disables the chart.
+                "readme.chart" => {
+                    chart = Some(
+                        Some(entry.string()?.trim())
+                            .filter(|x| !x.is_empty())
+                            .map(String::from),
+                    )
+                }
+                key => {
+                    return Err(Error::Syntax {
+                        line: entry.line,
+                        message: format!("unknown setting `{key}`."),
+                    });
+                }
+            }
+        }
+
+        if config.bench.max_samples < config.bench.min_samples {
+            return Err(Error::Invalid {
+                key: "bench.max_samples".into(),
+                line: None,
+                message: format!(
+                    "must not be below `bench.min_samples` ({}).",
+                    config.bench.min_samples
+                ),
+            });
+        }
+
+        config.table.chart = chart.unwrap_or_else(|| Some(default_chart_path(&config.data_dir)));
+
+        Ok(config)
+    }
+
+    /// Apply the settings that can be overridden by environment variables, e.g. `AOC_YEAR`.
+    fn apply_env(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        let non_empty = |key: &str| {
+            var(key)
+                .map(|x| x.trim().to_string())
+                .filter(|x| !x.is_empty())
+        };
+
+        if let Some(year) = non_empty(YEAR_ENV) {
+            let invalid = |message: String| Error::Invalid {
+                key: YEAR_ENV.into(),
+                line: None,
+                message,
+            };
+
+            let year = year
+                .parse()
+                .map_err(|_| invalid(format!("expected a year, got \"{year}\".")))?;
+            self.year = Some(parse_year(year).map_err(invalid)?);
+        }
+
+        if let Some(template) = non_empty(TEMPLATE_ENV) {
+            self.template = Some(template);
+        }
+
+        if let Some(color) = non_empty(COLOR_ENV) {
+            self.color = color.parse().map_err(|message| Error::Invalid {
+                key: COLOR_ENV.into(),
+                line: None,
+                message,
+            })?;
+        }
+
+        Ok(self)
+    }
+}
+
+impl std::str::FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "expected `auto`, `always` or `never`, got \"{s}\"."
+            )),
+        }
+    }
+}
+
+/// Read the configuration from `aoc.toml` and the environment. It is only read once, later calls return the same result.
+pub fn load() -> Result<&'static Config, &'static Error> {
+    CONFIG
+        .get_or_init(|| {
+            let config = match fs::read_to_string(CONFIG_PATH) {
+                Ok(source) => Config::parse(&source)?,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+                Err(e) => return Err(Error::IO(e)),
+            };
+
+            config.apply_env(|key| env::var(key).ok())
+        })
+        .as_ref()
+}
+
+/// The configuration of the project.
+/// Commands check it on startup, see [`load`]. Solutions run without a command, e.g. with `cargo run --bin`,
+/// fall back to the defaults with a warning if it is invalid.
+pub fn get() -> &'static Config {
+    load().unwrap_or_else(|e| {
+        FALLBACK.get_or_init(|| {
+            eprintln!("Warning: using the default settings, {e}");
+            Config::default()
+        })
+    })
+}
+
+fn parse_year(year: i64) -> Result<u16, String> {
+    u16::try_from(year)
+        .ok()
+        .filter(|year| *year >= FIRST_YEAR)
+        .ok_or_else(|| format!("expected a year since {FIRST_YEAR}, got {year}."))
+}
+
+fn default_chart_path(data_dir: &str) -> String {
+    Path::new(data_dir)
+        .join(DEFAULT_CHART_FILE)
+        .display()
+        .to_string()
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => Path::new(&home).join(rest).display().to_string(),
+        _ => path.into(),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(x) => write!(f, "\"{x}\""),
+            Value::Integer(x) => write!(f, "{x}"),
+            Value::Boolean(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+/// A setting of the file. Keys of tables are prefixed with the table name, e.g. `bench.budget_ms`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Entry {
+    key: String,
+    value: Value,
+    line: usize,
+}
+
+impl Entry {
+    fn invalid(&self, message: impl Into<String>) -> Error {
+        Error::Invalid {
+            key: self.key.clone(),
+            line: Some(self.line),
+            message: message.into(),
+        }
+    }
+
+    fn string(&self) -> Result<&str, Error> {
+        match &self.value {
+            Value::String(x) => Ok(x),
+            value => Err(self.invalid(format!("expected a string, got {value}."))),
+        }
+    }
+
+    fn non_empty_string(&self) -> Result<String, Error> {
+        match self.string()?.trim() {
+            "" => Err(self.invalid("must not be empty.")),
+            x => Ok(x.into()),
+        }
+    }
+
+    fn integer(&self) -> Result<i64, Error> {
+        match &self.value {
+            Value::Integer(x) => Ok(*x),
+            value => Err(self.invalid(format!("expected an integer, got {value}."))),
+        }
+    }
+
+    fn positive_integer(&self) -> Result<u64, Error> {
+        let value = self.integer()?;
+
+        u64::try_from(value)
+            .ok()
+            .filter(|x| *x > 0)
+            .ok_or_else(|| self.invalid(format!("expected a positive integer, got {value}.")))
+    }
+}
+
+/// Parse `key = value` lines, `[table]` headers and `#` comments.
+fn parse_entries(source: &str) -> Result<Vec<Entry>, Error> {
+    let mut entries: Vec<Entry> = vec![];
+    let mut table: Option<String> = None;
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let syntax_error = |message: String| Error::Syntax {
+            line: line_number,
+            message,
+        };
+
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with("[[") {
+            return Err(syntax_error(unsupported("arrays of tables")));
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            if name.contains('.') {
+                return Err(syntax_error(unsupported("nested tables")));
+            }
+
+            let name = name
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|x| is_bare_key(x))
+                .ok_or_else(|| syntax_error(format!("invalid table header `{line}`.")))?;
+            table = Some(name.into());
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| syntax_error(format!("expected `key = value`, got `{line}`.")))?;
+
+        let key = key.trim();
+        if key.contains('.') {
+            return Err(syntax_error(unsupported("dotted keys")));
+        }
+        if key.starts_with(['"', '\'']) {
+            return Err(syntax_error(unsupported("quoted keys")));
+        }
+        if !is_bare_key(key) {
+            return Err(syntax_error(format!("invalid key `{key}`.")));
+        }
+
+        let key = match &table {
+            Some(table) => format!("{table}.{key}"),
+            None => key.into(),
+        };
+
+        if entries.iter().any(|entry| entry.key == key) {
+            return Err(syntax_error(format!("duplicate setting `{key}`.")));
+        }
+
+        entries.push(Entry {
+            value: parse_value(value.trim()).map_err(syntax_error)?,
+            key,
+            line: line_number,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Error message for valid TOML that is outside the supported subset.
+fn unsupported(feature: &str) -> String {
+    format!(
+        "{feature} are not supported in {CONFIG_PATH}, see the readme for the supported subset of TOML."
+    )
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Remove a trailing `#` comment, unless the `#` is part of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+fn parse_value(value: &str) -> Result<Value, String> {
+    match value {
+        "true" => return Ok(Value::Boolean(true)),
+        "false" => return Ok(Value::Boolean(false)),
+        _ => {}
+    }
+
+    let feature = match value.chars().next() {
+        Some('[') => Some("arrays"),
+        Some('{') => Some("inline tables"),
+        Some('\'') => Some("literal strings"),
+        _ if value.starts_with("\"\"\"") => Some("multi-line strings"),
+        _ => None,
+    };
+    if let Some(feature) = feature {
+        return Err(unsupported(feature));
+    }
+
+    if let Some(string) = value.strip_prefix('"') {
+        let string = string
+            .strip_suffix('"')
+            .ok_or_else(|| format!("unterminated string {value}."))?;
+        return parse_string(string);
+    }
+
+    // NOTE: TOML allows underscores between digits, e.g. `10_000`.
+    value
+        .replace('_', "")
+        .parse()
+        .map(Value::Integer)
+        .map_err(|_| format!("expected a string, integer or boolean, got `{value}`."))
+}
+
+fn parse_string(string: &str) -> Result<Value, String> {
+    let mut result = String::new();
+    let mut chars = string.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\\') => result.push('\\'),
+                Some('"') => result.push('"'),
+                Some('b') => result.push('\u{8}'),
+                Some('f') => result.push('\u{c}'),
+                Some('n') => result.push('\n'),
+                Some('r') => result.push('\r'),
+                Some('t') => result.push('\t'),
+                Some(c @ ('u' | 'U')) => {
+                    let digits = if c == 'u' { 4 } else { 8 };
+                    let hex: String = chars.by_ref().take(digits).collect();
+                    let escaped = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .filter(|_| hex.len() == digits)
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| format!("invalid escape sequence `\\{c}{hex}`."))?;
+                    result.push(escaped);
+                }
+                Some(c) => return Err(format!("unsupported escape sequence `\\{c}`.")),
+                None => return Err("unterminated escape sequence.".into()),
+            },
+            '"' => return Err(format!("unexpected `\"` in string \"{string}\".")),
+            c => result.push(c),
+        }
+    }
+
+    Ok(Value::String(result))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{ColorChoice, Config, Error, TableLayout};
+    use crate::template::readme_benchmarks::{Column, Sort};
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            r#"
+# Settings of the template.
+year = 2024
+template = "grid" # a comment
+color = "never"
+
+[paths]
+data = "aoc-data"
+readme = "docs/#2024.md"
+
+[bench]
+budget_ms = 500
+max_samples = 1_000
+
+[readme]
+columns = "day, share,memory"
+sort = "slowest"
+table = "2024"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                year: Some(2024),
+                template: Some("grid".into()),
+                color: ColorChoice::Never,
+                data_dir: "aoc-data".into(),
+                readme: "docs/#2024.md".into(),
+                bench: super::BenchConfig {
+                    budget: Duration::from_millis(500),
+                    min_samples: 10,
+                    max_samples: 1000,
+                },
+                table: TableLayout {
+                    columns: vec![Column::Day, Column::Share, Column::Memory],
+                    sort: Sort::Slowest,
+                    name: Some("2024".into()),
+                    chart: Some("aoc-data/benchmarks.svg".into()),
+                },
+                ..Config::default()
+            }
+        );
+        assert_eq!(
+            config.data_path("inputs", "01.txt"),
+            "aoc-data/inputs/01.txt"
+        );
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert_eq!(
+            Config::default().table.chart.as_deref(),
+            Some("data/benchmarks.svg")
+        );
+        assert_eq!(
+            Config::parse("[readme]\nchart = \"\"").unwrap().table.chart,
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let error = |source: &str| Config::parse(source).unwrap_err().to_string();
+
+        assert_eq!(
+            error("year = \"2024\""),
+            "aoc.toml:1: invalid value for `year`: expected an integer, got \"2024\"."
+        );
+        assert_eq!(
+            error("\nyear = 24"),
+            "aoc.toml:2: invalid value for `year`: expected a year since 2015, got 24."
+        );
+        assert_eq!(
+            error("color = \"blue\""),
+            "aoc.toml:1: invalid value for `color`: expected `auto`, `always` or `never`, got \"blue\"."
+        );
+        assert_eq!(
+            error("[bench]\nbudget_ms = 0"),
+            "aoc.toml:2: invalid value for `bench.budget_ms`: expected a positive integer, got 0."
+        );
+        assert_eq!(
+            error("[bench]\nmin_samples = 20\nmax_samples = 10"),
+            "invalid value for `bench.max_samples`: must not be below `bench.min_samples` (20)."
+        );
+        assert_eq!(
+            error("[readme]\ncolumns = \"day,foo\""),
+            "aoc.toml:2: invalid value for `readme.columns`: unknown column \"foo\", expected one of: day, title, stars, parse, part_1, part_2, cold_1, cold_2, samples, spread, memory, share."
+        );
+        assert_eq!(
+            error("[readme]\nsort = \"fastest\""),
+            "aoc.toml:2: invalid value for `readme.sort`: expected `day` or `slowest`, got \"fastest\"."
+        );
+        assert_eq!(error("yaer = 2024"), "aoc.toml:1: unknown setting `yaer`.");
+        assert_eq!(
+            error("year = 2024\nyear = 2025"),
+            "aoc.toml:2: duplicate setting `year`."
+        );
+        assert_eq!(
+            error("template = \"grid"),
+            "aoc.toml:1: unterminated string \"grid."
+        );
+        assert!(matches!(
+            Config::parse("[bench"),
+            Err(Error::Syntax { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn parses_escaped_strings() {
+        let config = Config::parse(
+            r#"template = "say \"hi\" # not a comment" # a comment
+[paths]
+data = "C:\\aoc\u00e9""#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.template.as_deref(),
+            Some("say \"hi\" # not a comment")
+        );
+        assert_eq!(config.data_dir, "C:\\aocé");
+    }
+
+    #[test]
+    fn rejects_unsupported_toml() {
+        let error = |source: &str| Config::parse(source).unwrap_err().to_string();
+        let unsupported = |line: usize, feature: &str| {
+            format!(
+                "aoc.toml:{line}: {feature} are not supported in aoc.toml, see the readme for the supported subset of TOML."
+            )
+        };
+
+        assert_eq!(
+            error("[readme]\ncolumns = [\n  \"day\",\n]"),
+            unsupported(2, "arrays")
+        );
+        assert_eq!(
+            error("bench = { budget_ms = 500 }"),
+            unsupported(1, "inline tables")
+        );
+        assert_eq!(
+            error("bench.budget_ms = 500"),
+            unsupported(1, "dotted keys")
+        );
+        assert_eq!(error("\"year\" = 2024"), unsupported(1, "quoted keys"));
+        assert_eq!(
+            error("template = 'grid'"),
+            unsupported(1, "literal strings")
+        );
+        assert_eq!(
+            error("template = \"\"\"grid\"\"\""),
+            unsupported(1, "multi-line strings")
+        );
+        assert_eq!(error("[readme.layout]"), unsupported(1, "nested tables"));
+        assert_eq!(error("[[readme]]"), unsupported(1, "arrays of tables"));
+        assert_eq!(
+            error("template = \"\\u12\""),
+            "aoc.toml:1: invalid escape sequence `\\u12`."
+        );
+    }
+
+    #[test]
+    fn applies_environment() {
+        let env = |year: &'static str| {
+            move |key: &str| match key {
+                "AOC_YEAR" => Some(year.to_string()),
+                "AOC_TEMPLATE" => Some("graph".to_string()),
+                _ => None,
+            }
+        };
+
+        let config = Config::default().apply_env(env("2023")).unwrap();
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.template.as_deref(), Some("graph"));
+
+        assert_eq!(
+            Config::default()
+                .apply_env(env("last year"))
+                .unwrap_err()
+                .to_string(),
+            "invalid value for `AOC_YEAR`: expected a year, got \"last year\"."
+        );
+    }
+}
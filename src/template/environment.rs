@@ -0,0 +1,300 @@
+/// Module that describes the machine and toolchain timings were measured with.
+use std::{env, fs, process::Command, thread};
+
+use crate::template::affinity::{Pinning, governor};
+use crate::template::run_multi::RunOptions;
+
+/// Cargo configuration files of the project that `build.rustflags` is read from, see [`rustflags`].
+const CARGO_CONFIG_FILES: [&str; 2] = [".cargo/config.toml", ".cargo/config"];
+
+/// The machine and toolchain a set of timings was measured with.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Environment {
+    /// CPU model name, e.g. `AMD Ryzen 7 5800X 8-Core Processor`.
+    pub cpu: Option<String>,
+    /// Number of logical cores.
+    pub cores: Option<usize>,
+    /// Operating system and architecture, e.g. `linux x86_64`.
+    pub os: String,
+    /// Kernel release, e.g. `6.5.0-14-generic`.
+    pub kernel: Option<String>,
+    /// Output of `rustc -V`.
+    pub rustc: Option<String>,
+    pub profile: String,
+    /// Compiler flags the solutions were built with, e.g. `-C target-cpu=native`. See [`rustflags`].
+    pub rustflags: Option<String>,
+    /// CPU core and priority the solutions were run with.
+    pub pinning: Pinning,
+    /// Frequency governor of the pinned CPU core, e.g. `performance`.
+    pub governor: Option<String>,
+}
+
+impl Environment {
+    /// Collect the environment of a run with the given options.
+    pub fn collect(options: &RunOptions) -> Self {
+        let profile = options.profile.as_ref();
+        let pinning = options.pinning;
+
+        Environment {
+            cpu: cpu_model(),
+            cores: thread::available_parallelism().map(usize::from).ok(),
+            os: format!("{} {}", env::consts::OS, env::consts::ARCH),
+            kernel: fs::read_to_string("/proc/sys/kernel/osrelease")
+                .ok()
+                .map(|x| x.trim().to_string())
+                .or_else(|| command_output("uname", &["-r"])),
+            rustc: command_output("rustc", &["-V"]),
+            profile: profile.map_or_else(
+                || if options.is_release { "release" } else { "dev" }.into(),
+                |profile| profile.profile.clone(),
+            ),
+            rustflags: profile
+                .and_then(|profile| profile.rustflags())
+                .or_else(rustflags),
+            pinning,
+            governor: pinning.cpu.and_then(governor),
+        }
+    }
+
+    /// Describe the environment in a single line, e.g. for the readme.
+    pub fn describe(&self) -> String {
+        let mut parts = vec![];
+
+        let cores = self.cores.map(|cores| match cores {
+            1 => "1 core".to_string(),
+            cores => format!("{cores} cores"),
+        });
+
+        match (&self.cpu, cores) {
+            (Some(cpu), Some(cores)) => parts.push(format!("{cpu} ({cores})")),
+            (Some(cpu), None) => parts.push(cpu.clone()),
+            (None, Some(cores)) => parts.push(cores),
+            (None, None) => {}
+        }
+
+        match &self.kernel {
+            Some(kernel) => parts.push(format!("{} {kernel}", self.os)),
+            None => parts.push(self.os.clone()),
+        }
+
+        if let Some(rustc) = &self.rustc {
+            // NOTE: strip the commit hash and date from `rustc 1.83.0 (90b35a623 2024-11-26)`.
+            parts.push(rustc.split(" (").next().unwrap_or(rustc).to_string());
+        }
+
+        parts.push(format!("`{}` profile", self.profile));
+
+        if let Some(rustflags) = &self.rustflags {
+            parts.push(format!("`RUSTFLAGS=\"{rustflags}\"`"));
+        }
+
+        if let Some(pinning) = self.pinning.describe() {
+            parts.push(pinning);
+        }
+
+        if let Some(governor) = &self.governor {
+            parts.push(format!("`{governor}` governor"));
+        }
+
+        parts.join(", ")
+    }
+}
+
+/// The compiler flags cargo builds with, resolved in the order cargo uses: `CARGO_ENCODED_RUSTFLAGS`, `RUSTFLAGS`,
+/// `CARGO_BUILD_RUSTFLAGS` and `build.rustflags` of the cargo configuration. `target.<triple>.rustflags` is not resolved.
+pub fn rustflags() -> Option<String> {
+    if let Ok(flags) = env::var("CARGO_ENCODED_RUSTFLAGS") {
+        return non_empty(&flags.split('\x1f').collect::<Vec<_>>().join(" "));
+    }
+
+    if let Some(flags) = ["RUSTFLAGS", "CARGO_BUILD_RUSTFLAGS"]
+        .iter()
+        .find_map(|key| env::var(key).ok())
+    {
+        return non_empty(&flags);
+    }
+
+    // NOTE: `cargo config` is only available on nightly. Elsewhere, the configuration of the project is read instead.
+    command_output(
+        "cargo",
+        &[
+            "-Zunstable-options",
+            "config",
+            "get",
+            "build.rustflags",
+            "--format",
+            "json-value",
+        ],
+    )
+    .and_then(|value| parse_flags(&value))
+    .or_else(|| {
+        CARGO_CONFIG_FILES
+            .iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+            .and_then(|config| config_rustflags(&config))
+    })
+}
+
+/// Read `build.rustflags` from a cargo configuration file.
+fn config_rustflags(config: &str) -> Option<String> {
+    let mut table = "";
+    let mut lines = config.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+
+        if let Some(header) = line.strip_prefix('[') {
+            table = header.trim_end_matches(']').trim();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        let key = key.trim();
+        if !(table == "build" && key == "rustflags" || table.is_empty() && key == "build.rustflags")
+        {
+            continue;
+        }
+
+        // NOTE: arrays may span several lines.
+        let mut value = value.trim().to_string();
+        if value.starts_with('[') {
+            while !value.contains(']') {
+                let Some(line) = lines.next() else {
+                    break;
+                };
+                value.push_str(line.split('#').next().unwrap_or_default());
+            }
+        }
+
+        return parse_flags(&value);
+    }
+
+    None
+}
+
+/// Parse flags given as a string of space-separated flags or as an array of strings.
+fn parse_flags(value: &str) -> Option<String> {
+    let value = value.trim();
+
+    if value.starts_with('[') {
+        let flags: Vec<&str> = value.split(['"', '\'']).skip(1).step_by(2).collect();
+        return non_empty(&flags.join(" "));
+    }
+
+    non_empty(value.trim_matches(['"', '\'']))
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok();
+
+    cpuinfo
+        .as_deref()
+        .and_then(|cpuinfo| {
+            // NOTE: x86 uses `model name`, some ARM kernels only report `Model` or `Hardware`.
+            ["model name", "Model", "Hardware"].iter().find_map(|key| {
+                cpuinfo.lines().find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    (name.trim() == *key && !value.trim().is_empty())
+                        .then(|| value.trim().to_string())
+                })
+            })
+        })
+        .or_else(|| command_output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+}
+
+/// Run a command and return its trimmed stdout, if it succeeded and printed anything.
+pub fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let stdout = stdout.trim();
+    (!stdout.is_empty()).then(|| stdout.to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Environment, config_rustflags, parse_flags};
+    use crate::template::Pinning;
+
+    #[test]
+    fn reads_rustflags_from_cargo_config() {
+        assert_eq!(
+            config_rustflags("[alias]\nrustflags = \"no\"\n\n[build]\nrustflags = [\"-C\", \"target-cpu=native\"] # fast\n")
+                .as_deref(),
+            Some("-C target-cpu=native")
+        );
+        assert_eq!(
+            config_rustflags("[build]\nrustflags = [\n    \"-C\",\n    \"opt-level=3\",\n]\n")
+                .as_deref(),
+            Some("-C opt-level=3")
+        );
+        assert_eq!(
+            config_rustflags("build.rustflags = \"-C target-cpu=native\"").as_deref(),
+            Some("-C target-cpu=native")
+        );
+        assert_eq!(config_rustflags("[build]\njobs = 4"), None);
+        assert_eq!(parse_flags("[]"), None);
+    }
+
+    #[test]
+    fn describes_environments() {
+        let environment = Environment {
+            cpu: Some("AMD Ryzen 7 5800X 8-Core Processor".into()),
+            cores: Some(16),
+            os: "linux x86_64".into(),
+            kernel: Some("6.5.0".into()),
+            rustc: Some("rustc 1.83.0 (90b35a623 2024-11-26)".into()),
+            profile: "release".into(),
+            rustflags: Some("-C target-cpu=native".into()),
+            pinning: Pinning::default(),
+            governor: None,
+        };
+
+        assert_eq!(
+            environment.describe(),
+            "AMD Ryzen 7 5800X 8-Core Processor (16 cores), linux x86_64 6.5.0, rustc 1.83.0, `release` profile, `RUSTFLAGS=\"-C target-cpu=native\"`"
+        );
+    }
+
+    #[test]
+    fn describes_partial_environments() {
+        let environment = Environment {
+            os: "windows x86_64".into(),
+            profile: "release".into(),
+            ..Environment::default()
+        };
+
+        assert_eq!(environment.describe(), "windows x86_64, `release` profile");
+    }
+
+    #[test]
+    fn describes_pinned_environments() {
+        let environment = Environment {
+            os: "linux x86_64".into(),
+            profile: "release".into(),
+            pinning: Pinning {
+                cpu: Some(2),
+                high_priority: false,
+            },
+            governor: Some("powersave".into()),
+            ..Environment::default()
+        };
+
+        assert_eq!(
+            environment.describe(),
+            "linux x86_64, `release` profile, pinned to CPU 2, `powersave` governor"
+        );
+    }
+}
@@ -0,0 +1,412 @@
+/// Module that takes the examples and their answers from downloaded puzzle descriptions, so they do not have to be
+/// copied by hand. In the markdown written by aoc-cli, examples are code blocks and answers are emphasized code,
+/// e.g. "a total distance of `*11*`!".
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::Path,
+};
+
+use crate::template::{Day, aoc_cli, config, templates::format_answer};
+
+/// Heading that starts the description of the second part.
+static PART_TWO_MARKER: &str = "--- Part Two ---";
+/// Line that holds the accepted answer of a part, which is not an example answer.
+static ANSWER_MARKER: &str = "Your puzzle answer was";
+
+/// A code block in the description of a part.
+#[derive(Clone, Debug, PartialEq)]
+struct CodeBlock {
+    content: String,
+    /// Whether the text right before the block mentions an example, e.g. "For example:".
+    is_introduced: bool,
+}
+
+/// The code blocks and the example answer found in the description of a part.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct PartExamples {
+    blocks: Vec<CodeBlock>,
+    answer: Option<String>,
+}
+
+impl PartExamples {
+    /// The example, if it can be told apart from the other code blocks: it is either the only block,
+    /// or the only one introduced by a sentence mentioning an example.
+    fn obvious_example(&self) -> Option<&str> {
+        if let [block] = self.blocks.as_slice() {
+            return Some(&block.content);
+        }
+
+        let mut introduced = self.blocks.iter().filter(|b| b.is_introduced);
+        match (introduced.next(), introduced.next()) {
+            (Some(block), None) => Some(&block.content),
+            _ => None,
+        }
+    }
+}
+
+/// Read the answers to the examples of a day from its puzzle description. Unknown if the puzzle was not downloaded.
+pub fn read_answers(day: Day) -> [Option<String>; 2] {
+    let parts = fs::read_to_string(aoc_cli::get_puzzle_path(day))
+        .map(|puzzle| parse(&puzzle))
+        .unwrap_or_default();
+
+    [1, 2].map(|part| part_answer(&parts, part))
+}
+
+/// Write the examples of a day from its puzzle description to `data/examples`, and fill their answers into the tests
+/// of its solution. Existing example files are not overwritten. If the example of a part can not be told apart from
+/// other code blocks, the user is asked to choose one.
+pub fn update(day: Day) {
+    let Ok(puzzle) = fs::read_to_string(aoc_cli::get_puzzle_path(day)) else {
+        return;
+    };
+
+    let parts = parse(&puzzle);
+
+    for (part, examples) in (1..).zip(&parts) {
+        let path = example_path(day, part);
+
+        if !is_missing_or_empty(&path) {
+            continue;
+        }
+
+        let Some(example) = choose_example(examples, part) else {
+            continue;
+        };
+
+        // NOTE: most second parts reuse the example of the first part, which needs no file of its own.
+        if part == 2 && fs::read_to_string(example_path(day, 1)).ok().as_ref() == Some(&example) {
+            continue;
+        }
+
+        match fs::write(&path, example) {
+            Ok(()) => println!("Wrote example of part {part} to \"{path}\""),
+            Err(e) => eprintln!("Failed to write example file \"{path}\": {e}"),
+        }
+    }
+
+    update_tests(day);
+}
+
+/// Fill the known example answers into the tests of a day's solution, for tests that still expect `None`.
+/// If the second part has an example of its own, its test is changed to read it.
+pub fn update_tests(day: Day) {
+    let path = format!("src/bin/{day}.rs");
+
+    let Ok(source) = fs::read_to_string(&path) else {
+        return;
+    };
+
+    let has_part_two_example = !is_missing_or_empty(&example_path(day, 2));
+    let updated = fill_tests(&source, &read_answers(day), has_part_two_example);
+
+    if updated != source {
+        match fs::write(&path, updated) {
+            Ok(()) => println!("Filled the examples into the tests in \"{path}\""),
+            Err(e) => eprintln!("Failed to update the tests in \"{path}\": {e}"),
+        }
+    }
+}
+
+fn example_path(day: Day, part: u8) -> String {
+    let file = match part {
+        1 => format!("{day}.txt"),
+        part => format!("{day}-{part}.txt"),
+    };
+
+    config::get().data_path("examples", &file)
+}
+
+fn is_missing_or_empty(path: &str) -> bool {
+    !Path::new(path).exists() || fs::read_to_string(path).is_ok_and(|x| x.trim().is_empty())
+}
+
+fn part_answer(parts: &[PartExamples], part: u8) -> Option<String> {
+    parts.get(usize::from(part) - 1)?.answer.clone()
+}
+
+/// Split a puzzle description into its parts and collect the code blocks and the example answer of each.
+/// The second part is only present once the first one was solved.
+fn parse(puzzle: &str) -> Vec<PartExamples> {
+    let mut parts = vec![PartExamples::default()];
+    let mut block: Option<Vec<&str>> = None;
+    let mut previous_text = "";
+
+    for line in puzzle.lines() {
+        let Some(part) = parts.last_mut() else {
+            break;
+        };
+
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(lines) => {
+                    let content = lines.join("\n").trim_end().to_string();
+                    if !content.trim().is_empty() {
+                        part.blocks.push(CodeBlock {
+                            content: content + "\n",
+                            is_introduced: previous_text.to_lowercase().contains("example"),
+                        });
+                    }
+                    previous_text = "";
+                }
+                None => block = Some(vec![]),
+            }
+            continue;
+        }
+
+        if let Some(lines) = &mut block {
+            lines.push(line);
+            continue;
+        }
+
+        if line.contains(PART_TWO_MARKER) {
+            parts.push(PartExamples::default());
+            previous_text = "";
+            continue;
+        }
+
+        if line.trim().is_empty() || line.contains(ANSWER_MARKER) {
+            continue;
+        }
+
+        previous_text = line;
+
+        // NOTE: the answer is usually the last emphasized value of a part, e.g. "a total distance of `*11*`!".
+        if let Some(answer) = emphasized_code(line).pop() {
+            part.answer = Some(answer);
+        }
+    }
+
+    parts
+}
+
+/// Values of emphasized code spans in a line, written as `` `*11*` `` or ``*`11`*``.
+fn emphasized_code(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut index = 0;
+
+    while index < line.len() {
+        let rest = &line[index..];
+
+        let close = if rest.starts_with("`*") {
+            "*`"
+        } else if rest.starts_with("*`") {
+            "`*"
+        } else {
+            index += rest.chars().next().map_or(1, char::len_utf8);
+            continue;
+        };
+
+        match rest[2..].split_once(close) {
+            Some((value, _)) if !value.is_empty() && !value.contains('`') => {
+                values.push(value.to_string());
+                index += 2 + value.len() + close.len();
+            }
+            _ => index += 2,
+        }
+    }
+
+    values
+}
+
+/// Pick the example of a part. If it is not obvious, ask the user. Without a terminal, the first candidate is used.
+fn choose_example(examples: &PartExamples, part: u8) -> Option<String> {
+    if let Some(example) = examples.obvious_example() {
+        return Some(example.to_string());
+    }
+
+    // NOTE: prefer blocks introduced as an example, e.g. when several follow "For example:".
+    let introduced: Vec<&CodeBlock> = examples.blocks.iter().filter(|b| b.is_introduced).collect();
+    let candidates = if introduced.len() > 1 {
+        introduced
+    } else {
+        examples.blocks.iter().collect()
+    };
+
+    let first = candidates.first()?;
+
+    if !io::stdin().is_terminal() {
+        println!(
+            "Found {} code blocks in part {part} of the puzzle, using the first one as example.",
+            candidates.len()
+        );
+        return Some(first.content.clone());
+    }
+
+    println!(
+        "Found {} code blocks in part {part} of the puzzle. Which one is the example?",
+        candidates.len()
+    );
+
+    for (i, block) in candidates.iter().enumerate() {
+        let lines = block.content.lines().count();
+        let preview: String = block
+            .content
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(40)
+            .collect();
+        println!("  [{}] {lines} line(s): {preview}", i + 1);
+    }
+
+    loop {
+        print!("Choose 1-{}, or 0 to skip [1]: ", candidates.len());
+        let _ = io::stdout().flush();
+
+        let mut choice = String::new();
+        if io::stdin().read_line(&mut choice).is_err() {
+            return None;
+        }
+
+        match choice.trim() {
+            "" => return Some(first.content.clone()),
+            "0" => return None,
+            choice => {
+                if let Some(block) = choice
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| candidates.get(i.wrapping_sub(1)))
+                {
+                    return Some(block.content.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Fill example answers into tests that expect `None`, and read the part two example from its own file if it has one.
+fn fill_tests(source: &str, answers: &[Option<String>; 2], has_part_two_example: bool) -> String {
+    let mut source = source.to_string();
+
+    for (test, answer) in ["fn test_part_one", "fn test_part_two"].iter().zip(answers) {
+        let Some(start) = source.find(test) else {
+            continue;
+        };
+
+        // NOTE: a test ends where the next one starts.
+        let end = source[start + test.len()..]
+            .find("fn test_")
+            .map_or(source.len(), |i| start + test.len() + i);
+
+        let mut body = source[start..end].to_string();
+
+        let expected = format_answer(answer.as_deref());
+        if expected != "None" {
+            body = body.replacen(
+                "assert_eq!(result, None);",
+                &format!("assert_eq!(result, {expected});"),
+                1,
+            );
+        }
+
+        if *test == "fn test_part_two" && has_part_two_example {
+            body = body.replacen(
+                "read_file(\"examples\", DAY)",
+                "read_file_part(\"examples\", DAY, 2)",
+                1,
+            );
+        }
+
+        source.replace_range(start..end, &body);
+    }
+
+    source
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasized_code, fill_tests, parse};
+
+    const PUZZLE: &str = r"## \--- Day 1: Historian Hysteria ---
+
+Throughout the Chief's office, the lists are written like this:
+
+```
+Left Right
+```
+
+For example:
+
+```
+3   4
+4   3
+```
+
+In the example above, this is a total distance of `*11*`!
+
+Your puzzle answer was `2031679`.
+
+## \--- Part Two ---
+
+Once again consider your left and right lists, the similarity score is `9` + `4` + *`0`* = `*31*`.
+
+Your puzzle answer was `19678534`.
+";
+
+    #[test]
+    fn parses_examples_and_answers() {
+        let parts = parse(PUZZLE);
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].blocks.len(), 2);
+        assert_eq!(parts[0].obvious_example(), Some("3   4\n4   3\n"));
+        assert_eq!(parts[0].answer.as_deref(), Some("11"));
+
+        assert!(parts[1].blocks.is_empty());
+        assert_eq!(parts[1].obvious_example(), None);
+        assert_eq!(parts[1].answer.as_deref(), Some("31"));
+    }
+
+    #[test]
+    fn finds_no_obvious_example_among_several() {
+        let parts = parse("For example:\n```\na\n```\nAnother example:\n```\nb\n```\n");
+        assert_eq!(parts[0].blocks.len(), 2);
+        assert_eq!(parts[0].obvious_example(), None);
+
+        let parts = parse("Some grid:\n```\na\n```\n");
+        assert_eq!(parts[0].obvious_example(), Some("a\n"));
+    }
+
+    #[test]
+    fn finds_emphasized_code() {
+        assert_eq!(
+            emphasized_code("`*11*` and *`XMAS`* but not `5`"),
+            vec!["11", "XMAS"]
+        );
+        assert!(emphasized_code("a *b* `c` ``*").is_empty());
+    }
+
+    #[test]
+    fn fills_tests() {
+        let source = r#"
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+"#;
+
+        let filled = fill_tests(source, &[Some("11".into()), None], false);
+        assert!(filled.contains("assert_eq!(result, Some(11));"));
+        assert_eq!(filled.matches("assert_eq!(result, None);").count(), 1);
+        assert!(!filled.contains("read_file_part"));
+
+        let filled = fill_tests(source, &[Some("XMAS".into()), Some("31".into())], true);
+        assert_eq!(
+            filled,
+            source.replace(
+                    "part_two(&advent_of_code::template::read_file(\"examples\", DAY));\n        assert_eq!(result, None);",
+                    "part_two(&advent_of_code::template::read_file_part(\"examples\", DAY, 2));\n        assert_eq!(result, Some(31));"
+                )
+        );
+    }
+}
@@ -0,0 +1,173 @@
+/// Module that exports stored timings, e.g. to paste them into a spreadsheet or notes.
+use std::{
+    fmt::Write,
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::readme_benchmarks::{self, TableConfig};
+use crate::template::timings::{PartTiming, Timings};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "json" => Ok(ExportFormat::Json),
+            x => Err(format!(
+                "unknown export format \"{x}\", expected `csv`, `md` or `json`."
+            )),
+        }
+    }
+}
+
+/// Timings that should be exported after a run. Printed to stdout if no path is given.
+#[derive(Clone, Debug)]
+pub struct ExportTarget {
+    pub format: ExportFormat,
+    pub path: Option<PathBuf>,
+}
+
+/// Write `timings` in the format of `target`.
+pub fn export(timings: &Timings, target: &ExportTarget) -> Result<(), io::Error> {
+    let content = match target.format {
+        ExportFormat::Csv => to_csv(timings),
+        ExportFormat::Markdown => {
+            // NOTE: the chart is only written alongside the readme.
+            let config = TableConfig {
+                chart: None,
+                ..TableConfig::from_config()
+            };
+            readme_benchmarks::construct_markdown(timings.clone(), &config)
+        }
+        ExportFormat::Json => JsonValue::from(timings.clone())
+            .format()
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e.to_string()))?,
+    };
+
+    match &target.path {
+        Some(path) => {
+            fs::write(path, content)?;
+            println!("Exported timings to \"{}\".", path.display());
+        }
+        None => {
+            println!();
+            print!("{content}");
+        }
+    }
+
+    Ok(())
+}
+
+/// One row per day with durations in nanoseconds. Missing values are left empty.
+fn to_csv(timings: &Timings) -> String {
+    let mut csv = String::from(
+        "day,part_1_nanos,part_1_stddev_nanos,part_1_samples,part_1_cold_nanos,part_2_nanos,part_2_stddev_nanos,part_2_samples,part_2_cold_nanos,parse_nanos,total_nanos,peak_memory_bytes\n",
+    );
+
+    let part = |timing: Option<&PartTiming>| {
+        let stats = timing.and_then(|t| t.stats);
+        [
+            timing.map(|t| t.nanos.to_string()),
+            stats.map(|s| s.stddev_nanos.to_string()),
+            stats.map(|s| s.samples.to_string()),
+            timing.and_then(|t| t.cold_nanos).map(|x| x.to_string()),
+        ]
+        .map(Option::unwrap_or_default)
+        .join(",")
+    };
+
+    for timing in &timings.data {
+        // NOTE: writing to a `String` can not fail.
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{}",
+            timing.day,
+            part(timing.part_1.as_ref()),
+            part(timing.part_2.as_ref()),
+            timing
+                .parse
+                .as_ref()
+                .map(|t| t.nanos.to_string())
+                .unwrap_or_default(),
+            timing.total_nanos,
+            timing
+                .peak_memory_bytes
+                .map(|x| x.to_string())
+                .unwrap_or_default(),
+        );
+    }
+
+    csv
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ExportFormat, to_csv};
+    use crate::{
+        day,
+        template::timings::{PartTiming, SampleStats, Timing, Timings},
+    };
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse(), Ok(ExportFormat::Csv));
+        assert_eq!("md".parse(), Ok(ExportFormat::Markdown));
+        assert_eq!("json".parse(), Ok(ExportFormat::Json));
+        assert!("xlsx".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some(PartTiming {
+                        nanos: 1500_f64,
+                        stats: Some(SampleStats {
+                            samples: 100,
+                            min_nanos: 1000_f64,
+                            max_nanos: 2000_f64,
+                            stddev_nanos: 12.5,
+                        }),
+                        cold_nanos: Some(8000_f64),
+                    }),
+                    part_2: None,
+                    parse: None,
+                    total_nanos: 1500_f64,
+                    peak_memory_bytes: Some(4096),
+                    history: vec![],
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some(PartTiming::from_nanos(10_f64)),
+                    part_2: Some(PartTiming::from_nanos(20_f64)),
+                    parse: Some(PartTiming::from_nanos(5_f64)),
+                    total_nanos: 30_f64,
+                    peak_memory_bytes: None,
+                    history: vec![],
+                },
+            ],
+            ..Timings::default()
+        };
+
+        let csv = to_csv(&timings);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "01,1500,12.5,100,8000,,,,,,1500,4096");
+        assert_eq!(lines[2], "02,10,,,,20,,,,5,30,");
+    }
+}
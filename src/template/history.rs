@@ -0,0 +1,322 @@
+/// Module that keeps past measurements of a day, so its timings can be followed over time.
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, Pinning, ansi,
+    environment::{Environment, command_output},
+    run_multi::RunOptions,
+    timings::{PartTiming, Timings, optional_part, optional_string, parse_optional_part},
+};
+
+/// Maximum number of history entries that are kept per day. Older entries are dropped first.
+pub const MAX_HISTORY_ENTRIES: usize = 50;
+
+/// The circumstances a set of timings was measured in.
+#[derive(Clone, Debug, Default)]
+pub struct RunContext {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the checked out git commit, if any.
+    pub commit: Option<String>,
+    pub environment: Environment,
+}
+
+impl RunContext {
+    /// Collect the context of a run with the given options.
+    pub fn collect(options: &RunOptions) -> Self {
+        RunContext {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            commit: command_output("git", &["rev-parse", "--short", "HEAD"]),
+            environment: Environment::collect(options),
+        }
+    }
+}
+
+/// A single past measurement of a day.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub context: RunContext,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    pub total_nanos: f64,
+}
+
+/// Print the stored history of a day, oldest entry first.
+pub fn print_history(day: Day, timings: &Timings) {
+    println!("{}Day {day} history{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    println!("--------------");
+
+    let entries = timings.get(day).map_or(&[][..], |t| &t.history);
+
+    if entries.is_empty() {
+        println!("No history stored yet. Run `cargo time {day} --store` to add an entry.");
+        return;
+    }
+
+    println!(
+        "{:<16}  {:<9}  {:<8}  {:>10}  {:>10}  rustc",
+        "Date", "Commit", "Profile", "Part 1", "Part 2"
+    );
+
+    for entry in entries {
+        let context = &entry.context;
+        let environment = &context.environment;
+
+        // NOTE: only print the version number, e.g. `1.83.0` for `rustc 1.83.0 (90b35a623 2024-11-26)`.
+        let rustc = environment
+            .rustc
+            .as_deref()
+            .and_then(|v| v.split_whitespace().nth(1))
+            .unwrap_or("-");
+
+        println!(
+            "{:<16}  {:<9}  {:<8}  {:>10}  {:>10}  {rustc}",
+            format_timestamp(context.timestamp),
+            context.commit.as_deref().unwrap_or("-"),
+            environment.profile,
+            entry
+                .part_1
+                .as_ref()
+                .map_or("-".into(), PartTiming::display),
+            entry
+                .part_2
+                .as_ref()
+                .map_or("-".into(), PartTiming::display),
+        );
+    }
+}
+
+/// Format seconds since the unix epoch as `YYYY-MM-DD HH:MM` (UTC).
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = i64::try_from(timestamp / 86_400).unwrap_or_default();
+    let seconds = timestamp % 86_400;
+
+    // convert days since epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.context.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            optional_string(value.context.commit.as_ref()),
+        );
+
+        let environment = &value.context.environment;
+        map.insert("cpu".into(), optional_string(environment.cpu.as_ref()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "cores".into(),
+            environment
+                .cores
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert("os".into(), JsonValue::String(environment.os.clone()));
+        map.insert(
+            "kernel".into(),
+            optional_string(environment.kernel.as_ref()),
+        );
+        map.insert("rustc".into(), optional_string(environment.rustc.as_ref()));
+        map.insert(
+            "profile".into(),
+            JsonValue::String(environment.profile.clone()),
+        );
+        map.insert(
+            "rustflags".into(),
+            optional_string(environment.rustflags.as_ref()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "pinned_cpu".into(),
+            environment
+                .pinning
+                .cpu
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert(
+            "high_priority".into(),
+            JsonValue::Boolean(environment.pinning.high_priority),
+        );
+        map.insert(
+            "governor".into(),
+            optional_string(environment.governor.as_ref()),
+        );
+        map.insert("part_1".into(), optional_part(value.part_1.as_ref()));
+        map.insert("part_2".into(), optional_part(value.part_2.as_ref()));
+        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let optional_string = |key: &str| -> Result<Option<String>, String> {
+            match json.get(key) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+                Some(_) => Err(format!("Expected history.{key} to be null or string.")),
+            }
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected history.timestamp to be a number.")? as u64;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected history.profile to be a string.")?
+            .clone();
+
+        let total_nanos = json
+            .get("total_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected history.total_nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let optional_count = |key: &str| -> Result<Option<usize>, String> {
+            match json.get(key) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::Number(x)) => Ok(Some(*x as usize)),
+                Some(_) => Err(format!("Expected history.{key} to be null or a number.")),
+            }
+        };
+
+        let high_priority = match json.get("high_priority") {
+            None | Some(JsonValue::Null) => false,
+            Some(JsonValue::Boolean(x)) => *x,
+            Some(_) => return Err("Expected history.high_priority to be a boolean.".into()),
+        };
+
+        // NOTE: entries stored before the environment was recorded only have `rustc` and `profile`.
+        Ok(HistoryEntry {
+            context: RunContext {
+                timestamp,
+                commit: optional_string("commit")?,
+                environment: Environment {
+                    cpu: optional_string("cpu")?,
+                    cores: optional_count("cores")?,
+                    os: optional_string("os")?.unwrap_or_default(),
+                    kernel: optional_string("kernel")?,
+                    rustc: optional_string("rustc")?,
+                    profile,
+                    rustflags: optional_string("rustflags")?,
+                    pinning: Pinning {
+                        cpu: optional_count("pinned_cpu")?,
+                        high_priority,
+                    },
+                    governor: optional_string("governor")?,
+                },
+            },
+            part_1: parse_optional_part(json.get("part_1").unwrap_or(&JsonValue::Null))?,
+            part_2: parse_optional_part(json.get("part_2").unwrap_or(&JsonValue::Null))?,
+            total_nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{HistoryEntry, RunContext, format_timestamp};
+    use crate::template::{Pinning, environment::Environment, timings::PartTiming};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_575_500), "2024-12-07 12:45");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = HistoryEntry {
+            context: RunContext {
+                timestamp: 1_733_575_500,
+                commit: Some("abc1234".into()),
+                environment: Environment {
+                    cpu: Some("Apple M1".into()),
+                    cores: Some(8),
+                    os: "macos aarch64".into(),
+                    profile: "release".into(),
+                    ..Environment::default()
+                },
+            },
+            part_1: Some(PartTiming::from_nanos(1_200_000_f64)),
+            part_2: None,
+            total_nanos: 1_200_000_f64,
+        };
+
+        let parsed = HistoryEntry::try_from(&JsonValue::from(&entry)).unwrap();
+        assert_eq!(parsed.context.timestamp, 1_733_575_500);
+        assert_eq!(parsed.context.commit, Some("abc1234".into()));
+        assert_eq!(parsed.context.environment, entry.context.environment);
+        assert_eq!(parsed.part_1, Some(PartTiming::from_nanos(1_200_000_f64)));
+        assert_eq!(parsed.part_2, None);
+    }
+
+    #[test]
+    fn roundtrips_pinning() {
+        let entry = HistoryEntry {
+            context: RunContext {
+                environment: Environment {
+                    profile: "release".into(),
+                    pinning: Pinning {
+                        cpu: Some(3),
+                        high_priority: true,
+                    },
+                    governor: Some("performance".into()),
+                    ..Environment::default()
+                },
+                ..RunContext::default()
+            },
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+        };
+
+        let parsed = HistoryEntry::try_from(&JsonValue::from(&entry)).unwrap();
+        assert_eq!(parsed.context.environment, entry.context.environment);
+    }
+}
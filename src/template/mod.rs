@@ -2,24 +2,56 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+#[cfg(feature = "registry")]
+pub mod registry;
 pub mod runner;
 
+pub use affinity::Pinning;
 pub use day::*;
+pub use export::{ExportFormat, ExportTarget};
+pub use profiles::{BuildProfile, parse_profiles};
+pub use report::ReportTarget;
+pub use timings::MergePolicy;
 
+mod affinity;
+mod answers;
+mod benchmark_chart;
+mod compare;
 mod day;
+mod environment;
+mod examples;
+mod export;
+mod history;
+mod profiles;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
 mod run_multi;
+mod templates;
 mod timings;
+mod worktree;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+
+/// The given escape code if output is colored, an empty string otherwise. See `color` in `aoc.toml`.
+#[must_use]
+pub fn ansi(code: &'static str) -> &'static str {
+    if config::get().use_color() { code } else { "" }
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(&config::get().data_dir)
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -29,7 +61,7 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(&config::get().data_dir)
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
@@ -39,19 +71,23 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, pass `parse = <function>` to additionally bench a function that parses the input.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, [$parse], [part_one, 1] [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, [$($parse:expr)?], $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +95,37 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// The entry of this solution in the in-process registry.
+        #[cfg(feature = "registry")]
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                day: DAY,
+                parse: $crate::solution!(@parse $($parse)?),
+                parts: &[$(
+                    $crate::template::registry::SolutionPart {
+                        part: $part,
+                        run: |input, is_timed| $crate::template::runner::measure_part($func, input, is_timed),
+                    },
+                )*],
+            };
+
         fn main() {
             use $crate::template::runner::*;
+            pin_cpu();
             let input = $crate::template::read_file("inputs", DAY);
+            $( run_parse($parse, &input); )?
             $( run_part($func, &input, DAY, $part); )*
+            print_peak_memory();
         }
     };
+
+    (@parse) => {
+        None
+    };
+    (@parse $parse:expr) => {
+        Some(|input: &str, is_timed: bool| {
+            $crate::template::runner::measure_parse($parse, input, is_timed)
+        })
+    };
 }
@@ -0,0 +1,255 @@
+/// Module that benches solutions under several cargo profiles and compiler flags, e.g. to decide whether LTO or
+/// `-C target-cpu=native` is worth enabling.
+use std::{path::PathBuf, str::FromStr};
+
+use crate::template::environment;
+use crate::template::timings::{Timings, format_nanos};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, ansi};
+
+/// Compiler flag that is added for `native` profiles.
+const NATIVE_RUSTFLAGS: &str = "-C target-cpu=native";
+
+/// A cargo profile to build solutions with, optionally for the CPU of this machine.
+/// Written as `<profile>` or `<profile>+native`, `native` is short for `release+native`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildProfile {
+    /// Name as given on the command line, e.g. `release-lto+native`.
+    pub name: String,
+    /// Cargo profile, e.g. `release-lto`.
+    pub profile: String,
+    /// Whether solutions are built with `-C target-cpu=native`.
+    pub native: bool,
+}
+
+impl BuildProfile {
+    /// `RUSTFLAGS` to build with. Flags cargo would use otherwise are kept, see [`environment::rustflags`].
+    pub fn rustflags(&self) -> Option<String> {
+        if !self.native {
+            return None;
+        }
+
+        match environment::rustflags() {
+            Some(flags) => Some(format!("{flags} {NATIVE_RUSTFLAGS}")),
+            None => Some(NATIVE_RUSTFLAGS.into()),
+        }
+    }
+
+    /// Target directory to build into, if it differs from the default one.
+    /// Cargo keeps profiles apart on its own, but changed `RUSTFLAGS` would invalidate the main build.
+    pub fn target_dir(&self) -> Option<PathBuf> {
+        self.native.then(|| PathBuf::from("target").join("native"))
+    }
+}
+
+impl FromStr for BuildProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+
+        let (profile, native) = match name {
+            "native" => ("release", true),
+            name => match name.strip_suffix("+native") {
+                Some(profile) => (profile, true),
+                None => (name, false),
+            },
+        };
+
+        if profile.is_empty()
+            || !profile
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "invalid profile \"{s}\", expected `<profile>`, `<profile>+native` or `native`."
+            ));
+        }
+
+        Ok(BuildProfile {
+            name: name.into(),
+            profile: profile.into(),
+            native,
+        })
+    }
+}
+
+/// Parse a comma-separated list of profiles, e.g. `release,release-lto,native`.
+pub fn parse_profiles(s: &str) -> Result<Vec<BuildProfile>, String> {
+    let profiles = s
+        .split(',')
+        .filter(|x| !x.trim().is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<BuildProfile>, _>>()?;
+
+    if profiles.is_empty() {
+        return Err("expected at least one profile.".into());
+    }
+
+    Ok(profiles)
+}
+
+/// Print the timings of every part under every profile, relative to the first profile.
+pub fn print_matrix(profiles: &[BuildProfile], timings: &[Timings]) {
+    println!("\n{}Profiles{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    println!("--------");
+
+    for line in format_matrix(profiles, timings) {
+        println!("{line}");
+    }
+}
+
+fn format_matrix(profiles: &[BuildProfile], timings: &[Timings]) -> Vec<String> {
+    let mut days: Vec<Day> = timings
+        .iter()
+        .flat_map(|t| t.data.iter().map(|x| x.day))
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    for day in days {
+        for part in 1..=2 {
+            let nanos: Vec<Option<f64>> = timings
+                .iter()
+                .map(|t| t.get(day).and_then(|x| x.part_nanos(part)))
+                .collect();
+
+            if nanos.iter().all(Option::is_none) {
+                continue;
+            }
+
+            let mut row = vec![day.to_string(), part.to_string()];
+            row.extend(format_cells(&nanos));
+            rows.push(row);
+        }
+    }
+
+    let totals: Vec<Option<f64>> = timings
+        .iter()
+        .map(|t| (!t.data.is_empty()).then(|| t.data.iter().map(|x| x.total_nanos).sum()))
+        .collect();
+
+    let mut total_row = vec!["Total".to_string(), String::new()];
+    total_row.extend(format_cells(&totals));
+    rows.push(total_row);
+
+    let mut header = vec!["Day".to_string(), "Part".to_string()];
+    header.extend(profiles.iter().map(|p| p.name.clone()));
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    [&header]
+        .into_iter()
+        .chain(rows.iter())
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    // NOTE: day and part are left-aligned, timings right-aligned.
+                    if i < 2 {
+                        format!("{cell:<width$}", width = widths[i])
+                    } else {
+                        format!("{cell:>width$}", width = widths[i])
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Format one timing per profile. All but the first show their change relative to the first profile.
+fn format_cells(nanos: &[Option<f64>]) -> Vec<String> {
+    let baseline = nanos.first().copied().flatten().filter(|x| *x > 0.0);
+
+    nanos
+        .iter()
+        .enumerate()
+        .map(|(i, nanos)| match (nanos, baseline) {
+            (None, _) => "-".into(),
+            (Some(nanos), Some(baseline)) if i > 0 => format!(
+                "{} ({:+.1}%)",
+                format_nanos(*nanos),
+                (nanos - baseline) / baseline * 100.0
+            ),
+            (Some(nanos), _) => format_nanos(*nanos),
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BuildProfile, format_matrix, parse_profiles};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn timings(part_1: f64, part_2: Option<f64>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(PartTiming::from_nanos(part_1)),
+                part_2: part_2.map(PartTiming::from_nanos),
+                parse: None,
+                total_nanos: part_1 + part_2.unwrap_or_default(),
+                peak_memory_bytes: None,
+                history: vec![],
+            }],
+            ..Timings::default()
+        }
+    }
+
+    #[test]
+    fn parses_profiles() {
+        let profiles = parse_profiles("release, release-lto,native,release-lto+native").unwrap();
+
+        assert_eq!(
+            profiles
+                .iter()
+                .map(|p| (p.profile.as_str(), p.native))
+                .collect::<Vec<_>>(),
+            vec![
+                ("release", false),
+                ("release-lto", false),
+                ("release", true),
+                ("release-lto", true)
+            ]
+        );
+        assert_eq!(profiles[3].name, "release-lto+native");
+
+        assert!(parse_profiles("").is_err());
+        assert!("+native".parse::<BuildProfile>().is_err());
+        assert!("release lto".parse::<BuildProfile>().is_err());
+    }
+
+    #[test]
+    fn formats_matrix_relative_to_first_profile() {
+        let profiles = parse_profiles("release,native").unwrap();
+        let lines = format_matrix(
+            &profiles,
+            &[timings(1000.0, Some(2000.0)), timings(500.0, None)],
+        );
+
+        assert_eq!(
+            lines,
+            vec![
+                "Day    Part  release            native",
+                "01     1       1.0µs  500.0ns (-50.0%)",
+                "01     2       2.0µs                 -",
+                "Total          3.0µs  500.0ns (-83.3%)",
+            ]
+        );
+    }
+}
@@ -1,13 +1,27 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, ErrorKind},
+    path::{Component, Path},
+    str::FromStr,
+};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::answers::{self, KnownAnswers};
+use crate::template::aoc_cli;
+use crate::template::benchmark_chart;
+use crate::template::environment::Environment;
+use crate::template::history::MAX_HISTORY_ENTRIES;
+use crate::template::timings::{MergePolicy, PartTiming, Timing, Timings, format_nanos};
+use crate::template::{Day, config};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static DEFAULT_FILE: &str = "README.md";
+
+/// Columns of the table unless `columns` is set in the `[readme]` table of `aoc.toml`.
+pub(crate) const DEFAULT_COLUMNS: [Column; 3] = [Column::Day, Column::Part1, Column::Part2];
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -20,6 +34,193 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Error {
+    /// Prefix the error message with the file it occurred in.
+    pub(crate) fn in_file(self, path: &str) -> Self {
+        match self {
+            Error::Parser(e) => Error::Parser(format!("{path}: {e}")),
+            Error::IO(e) => Error::IO(io::Error::new(e.kind(), format!("{path}: {e}"))),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A column of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Day,
+    /// Puzzle title, linked to adventofcode.com.
+    Title,
+    /// Stars earned, based on the answers in the downloaded puzzle description.
+    Stars,
+    /// Timing of the input parser, if the solution declares one.
+    Parse,
+    Part1,
+    Part2,
+    /// Duration of the first, unbenched run of part 1.
+    Cold1,
+    /// Duration of the first, unbenched run of part 2.
+    Cold2,
+    Samples,
+    /// Standard deviation of the samples.
+    Spread,
+    /// Peak memory usage of the solution binary.
+    Memory,
+    /// Share of the total runtime of all days.
+    Share,
+}
+
+impl Column {
+    const ALL: [Column; 12] = [
+        Column::Day,
+        Column::Title,
+        Column::Stars,
+        Column::Parse,
+        Column::Part1,
+        Column::Part2,
+        Column::Cold1,
+        Column::Cold2,
+        Column::Samples,
+        Column::Spread,
+        Column::Memory,
+        Column::Share,
+    ];
+
+    fn id(self) -> &'static str {
+        match self {
+            Column::Day => "day",
+            Column::Title => "title",
+            Column::Stars => "stars",
+            Column::Parse => "parse",
+            Column::Part1 => "part_1",
+            Column::Part2 => "part_2",
+            Column::Cold1 => "cold_1",
+            Column::Cold2 => "cold_2",
+            Column::Samples => "samples",
+            Column::Spread => "spread",
+            Column::Memory => "memory",
+            Column::Share => "share",
+        }
+    }
+
+    fn header(self) -> &'static str {
+        match self {
+            Column::Day => "Day",
+            Column::Title => "Title",
+            Column::Stars => "Stars",
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Cold1 => "Part 1 (cold)",
+            Column::Cold2 => "Part 2 (cold)",
+            Column::Samples => "Samples",
+            Column::Spread => "Spread",
+            Column::Memory => "Memory",
+            Column::Share => "Share",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Column::ALL
+            .into_iter()
+            .find(|column| column.id() == s)
+            .ok_or_else(|| {
+                let ids: Vec<_> = Column::ALL.iter().map(|c| c.id()).collect();
+                format!(
+                    "unknown column \"{s}\", expected one of: {}.",
+                    ids.join(", ")
+                )
+            })
+    }
+}
+
+/// Row order of the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sort {
+    #[default]
+    Day,
+    /// Slowest day first, by total runtime.
+    Slowest,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Sort::Day),
+            "slowest" => Ok(Sort::Slowest),
+            _ => Err(format!("expected `day` or `slowest`, got \"{s}\".")),
+        }
+    }
+}
+
+/// Parse a comma-separated list of columns, e.g. `day,part_1,part_2`.
+pub(crate) fn parse_columns(columns: &str) -> Result<Vec<Column>, String> {
+    columns.split(',').map(|id| id.trim().parse()).collect()
+}
+
+/// Layout and location of the benchmark table.
+/// Configured with `paths.readme` and the `[readme]` table of `aoc.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableConfig {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Path of the benchmark chart, if one is rendered.
+    pub chart: Option<String>,
+    /// Markdown file the table is written to.
+    pub file: String,
+    /// Name of the table, e.g. `2024`. Named tables use their own markers, so one file can hold several tables.
+    pub name: Option<String>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        TableConfig {
+            columns: DEFAULT_COLUMNS.to_vec(),
+            sort: Sort::Day,
+            chart: None,
+            file: DEFAULT_FILE.into(),
+            name: None,
+        }
+    }
+}
+
+impl TableConfig {
+    /// Read the table layout from `aoc.toml`. The table is written to the same file as the stars.
+    pub fn from_config() -> Self {
+        let config = config::get();
+
+        TableConfig {
+            columns: config.table.columns.clone(),
+            sort: config.table.sort,
+            chart: config.table.chart.clone(),
+            file: config.readme.clone(),
+            name: config.table.name.clone(),
+        }
+    }
+
+    /// The marker that delimits this table, e.g. `<!--- benchmarking table 2024 --->` for a table named `2024`.
+    fn marker(&self) -> String {
+        match &self.name {
+            Some(name) => format!("<!--- benchmarking table {name} --->"),
+            None => MARKER.into(),
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
@@ -30,137 +231,554 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locate the section between two occurrences of `marker`, including the markers themselves.
+/// A single marker is treated as an empty section. Markers in code, i.e. fenced code blocks and `inline code`, do not
+/// count, so docs can mention them.
+pub(crate) fn locate_section(content: &str, marker: &str) -> Result<TablePosition, Error> {
+    let mut matches: Vec<usize> = vec![];
+    let mut in_code_block = false;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            // NOTE: a marker is in inline code if an odd number of backticks precede it on its line.
+            matches.extend(
+                line.match_indices(marker)
+                    .filter(|(i, _)| line[..*i].matches('`').count() % 2 == 0)
+                    .map(|(i, _)| offset + i),
+            );
+        }
+
+        offset += line.len();
+    }
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "found {} occurrences of marker `{marker}`, expected at most two.",
+            matches.len()
+        )));
+    }
+
+    let (Some(pos_start), Some(last)) = (matches.first(), matches.last()) else {
+        return Err(Error::Parser(format!("could not find marker `{marker}`.")));
+    };
+
+    Ok(TablePosition {
+        pos_start: *pos_start,
+        pos_end: last + marker.len(),
+    })
+}
+
+/// Replace the section between two occurrences of `marker` with `section`, which should start and end with `marker`.
+pub(crate) fn replace_section(
+    content: &mut String,
+    marker: &str,
+    section: &str,
+) -> Result<(), Error> {
+    let positions = locate_section(content, marker)?;
+    content.replace_range(positions.pos_start..positions.pos_end, section);
+    Ok(())
+}
+
+/// Read a markdown file that sections are written to. Missing files are created with an empty section.
+pub(crate) fn read_target(path: &str, marker: &str) -> Result<String, Error> {
+    match fs::read(path) {
+        Ok(content) => Ok(String::from_utf8_lossy(&content).to_string()),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            if let Some(parent) = Path::new(path).parent() {
+                fs::create_dir_all(parent)?;
+            }
+            Ok(format!("{marker}\n"))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Link to `target` from a markdown file at `file`, both relative to the repository root.
+fn relative_link(file: &str, target: &str) -> String {
+    let depth = Path::new(file).parent().map_or(0, |p| {
+        p.components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .count()
+    });
+
+    let target = target.trim_start_matches("./");
+
+    if depth == 0 {
+        format!("./{target}")
+    } else {
+        format!("{}{target}", "../".repeat(depth))
+    }
+}
+
+/// Describe the environments the timings were measured in, based on the latest history entry of each day.
+fn construct_footer(timings: &Timings) -> Vec<String> {
+    let mut groups: Vec<(&Environment, Vec<String>)> = vec![];
+
+    for timing in &timings.data {
+        let Some(entry) = timing.history.last() else {
+            continue;
+        };
+
+        let environment = &entry.context.environment;
+        let day = timing.day.into_inner().to_string();
+
+        match groups.iter_mut().find(|(e, _)| *e == environment) {
+            Some((_, days)) => days.push(day),
+            None => groups.push((environment, vec![day])),
+        }
+    }
+
+    match groups.as_slice() {
+        [] => vec![],
+        [(environment, _)] => vec![
+            String::new(),
+            format!("_Measured on {}._", environment.describe()),
+        ],
+        _ => {
+            let mut lines = vec![String::new(), "_Measured on:_".into(), String::new()];
+
+            for (environment, days) in &groups {
+                let label = if days.len() == 1 { "Day" } else { "Days" };
+                lines.push(format!(
+                    "- {label} {}: {}",
+                    days.join(", "),
+                    environment.describe()
+                ));
+            }
+
+            lines
+        }
+    }
+}
+
+/// Render the cell of a column for a single day.
+fn construct_cell(column: Column, timing: &Timing, total_nanos: f64, file: &str) -> String {
+    let day = timing.day;
+
+    match column {
+        Column::Day => format!(
+            "[Day {}]({})",
+            day.into_inner(),
+            relative_link(file, &get_path_for_bin(day))
+        ),
+        Column::Title => match (answers::read_title(day), aoc_cli::get_year()) {
+            (Some(title), Some(year)) => format!(
+                "[{title}](https://adventofcode.com/{year}/day/{})",
+                day.into_inner()
+            ),
+            (Some(title), None) => title,
+            (None, _) => "-".into(),
+        },
+        Column::Stars => {
+            let answers = KnownAnswers::read(day);
+            let stars = (1..=2).filter(|part| answers.get(*part).is_some()).count();
+            if stars == 0 {
+                "-".into()
+            } else {
+                "⭐".repeat(stars)
+            }
+        }
+        Column::Parse => format_timing(timing.parse.as_ref()),
+        Column::Part1 => format_timing(timing.part_1.as_ref()),
+        Column::Part2 => format_timing(timing.part_2.as_ref()),
+        Column::Cold1 => format_cold(timing.part_1.as_ref()),
+        Column::Cold2 => format_cold(timing.part_2.as_ref()),
+        Column::Samples => format_parts(timing, |t| t.stats.map(|stats| stats.samples.to_string())),
+        Column::Spread => format_parts(timing, |t| {
+            t.stats
+                .map(|stats| format!("±{}", format_nanos(stats.stddev_nanos)))
+        }),
+        Column::Memory => timing
+            .peak_memory_bytes
+            .map_or_else(|| "-".into(), format_bytes),
+        Column::Share => {
+            if total_nanos > 0.0 {
+                format!("{:.1}%", timing.total_nanos / total_nanos * 100.0)
+            } else {
+                "-".into()
+            }
+        }
+    }
+}
+
+/// Render the totals of a column, split per part.
+fn construct_total_cell(column: Column, timings: &[Timing]) -> String {
+    let sum = |part: fn(&Timing) -> Option<f64>| {
+        let nanos: Vec<f64> = timings.iter().filter_map(part).collect();
+        if nanos.is_empty() {
+            "-".into()
+        } else {
+            format!("`{}`", format_nanos(nanos.iter().sum()))
+        }
+    };
+
+    match column {
+        Column::Day => "**Total**".into(),
+        Column::Parse => sum(|t| t.parse.as_ref().map(|t| t.nanos)),
+        Column::Part1 => sum(|t| t.part_1.as_ref().map(|t| t.nanos)),
+        Column::Part2 => sum(|t| t.part_2.as_ref().map(|t| t.nanos)),
+        Column::Cold1 => sum(|t| t.part_1.as_ref().and_then(|t| t.cold_nanos)),
+        Column::Cold2 => sum(|t| t.part_2.as_ref().and_then(|t| t.cold_nanos)),
+        Column::Share => "100%".into(),
+        _ => String::new(),
+    }
+}
+
+fn format_timing(timing: Option<&PartTiming>) -> String {
+    timing.map_or_else(|| "-".into(), |t| format!("`{}`", t.display()))
+}
+
+fn format_cold(timing: Option<&PartTiming>) -> String {
+    timing
+        .and_then(|t| t.cold_nanos)
+        .map_or_else(|| "-".into(), |nanos| format!("`{}`", format_nanos(nanos)))
+}
+
+/// Format a value for both parts, e.g. `100 / 50`.
+fn format_parts(timing: &Timing, f: impl Fn(&PartTiming) -> Option<String>) -> String {
+    let part = |part: u8| timing.part(part).and_then(&f).unwrap_or_else(|| "-".into());
+    format!("{} / {}", part(1), part(2))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1} GiB", b as f64 / f64::from(1 << 30)),
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / f64::from(1 << 20)),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / f64::from(1 << 10)),
+        b => format!("{b} B"),
     }
+}
+
+fn construct_row(cells: &[String]) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> String {
+    let marker = config.marker();
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    let header = match &config.name {
+        Some(name) => format!("{prefix} Benchmarks ({name})"),
+        None => format!("{prefix} Benchmarks"),
+    };
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+    lines.extend(construct_body(timings, total_millis, config));
+    lines.push(marker);
 
-    Ok(TablePosition { pos_start, pos_end })
+    lines.join("\n")
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Render the benchmark table as a standalone markdown document, e.g. for exports.
+pub(crate) fn construct_markdown(timings: Timings, config: &TableConfig) -> String {
+    let total_millis = timings.total_millis();
+    let mut lines = construct_body(timings, total_millis, config);
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Render the rows, totals, chart and footer of the benchmark table.
+fn construct_body(timings: Timings, total_millis: f64, config: &TableConfig) -> Vec<String> {
+    let footer = construct_footer(&timings);
+    let policy = timings.policy;
+
+    let mut data = timings.data;
+
+    if config.sort == Sort::Slowest {
+        data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let total_nanos: f64 = data.iter().map(|t| t.total_nanos).sum();
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        construct_row(
+            &config
+                .columns
+                .iter()
+                .map(|c| c.header().to_string())
+                .collect::<Vec<_>>(),
+        ),
+        construct_row(&vec![":---:".to_string(); config.columns.len()]),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+    for timing in &data {
+        lines.push(construct_row(
+            &config
+                .columns
+                .iter()
+                .map(|c| construct_cell(*c, timing, total_nanos, &config.file))
+                .collect::<Vec<_>>(),
+        ));
+    }
+
+    if config.columns.iter().any(|c| {
+        matches!(
+            c,
+            Column::Parse | Column::Part1 | Column::Part2 | Column::Cold1 | Column::Cold2
+        )
+    }) {
+        lines.push(construct_row(
+            &config
+                .columns
+                .iter()
+                .map(|c| construct_total_cell(*c, &data))
+                .collect::<Vec<_>>(),
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
 
-    lines.join("\n")
+    // NOTE: the default policy is not mentioned to keep the table short.
+    match policy {
+        MergePolicy::Latest => {}
+        MergePolicy::Best => {
+            lines.push(String::new());
+            lines.push("_Timings are the fastest measurement per part (`best` policy)._".into());
+        }
+        MergePolicy::Mean => {
+            lines.push(String::new());
+            lines.push(format!(
+                "_Timings are the mean of up to the last {MAX_HISTORY_ENTRIES} measurements per part (`mean` policy)._"
+            ));
+        }
+    }
+
+    if let Some(chart) = &config.chart {
+        lines.push(String::new());
+        lines.push(format!(
+            "![Benchmark chart]({})",
+            relative_link(&config.file, chart)
+        ));
+    }
+
+    lines.extend(footer);
+    lines
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> Result<(), Error> {
+    let table = construct_table("##", timings, total_millis, config);
+    replace_section(s, &config.marker(), &table)
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let config = TableConfig::from_config();
+    let path = config.file.as_str();
+    let mut readme = read_target(path, &config.marker()).map_err(|e| e.in_file(path))?;
+
+    if let Some(chart) = &config.chart {
+        benchmark_chart::store(&timings, chart)?;
+    }
+
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    update_content(&mut readme, timings, total_millis, &config).map_err(|e| e.in_file(path))?;
+    fs::write(path, &readme).map_err(|e| Error::from(e).in_file(path))?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{
+        Column, MARKER, MAX_HISTORY_ENTRIES, Sort, TableConfig, relative_link, update_content,
+    };
+    use crate::{
+        day,
+        template::{
+            environment::Environment,
+            history::{HistoryEntry, RunContext},
+            timings::{MergePolicy, PartTiming, Timing, Timings},
+        },
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming::from_nanos(10_000_000_f64)),
+                    part_2: Some(PartTiming::from_nanos(20_000_000_f64)),
+                    parse: None,
                     total_nanos: 3e+10,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming::from_nanos(30_000_000_f64)),
+                    part_2: Some(PartTiming::from_nanos(40_000_000_f64)),
+                    parse: None,
                     total_nanos: 7e+10,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: Some(PartTiming::from_nanos(40_000_000_f64)),
+                    part_2: Some(PartTiming::from_nanos(50_000_000_f64)),
+                    parse: None,
                     total_nanos: 9e+10,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 },
             ],
+            ..Timings::default()
         }
     }
 
+    fn with_environments(timings: Timings, cpus: &[&str]) -> Timings {
+        let mut timings = timings;
+
+        for (timing, cpu) in timings.data.iter_mut().zip(cpus) {
+            timing.history.push(HistoryEntry {
+                context: RunContext {
+                    environment: Environment {
+                        cpu: Some((*cpu).into()),
+                        os: "linux x86_64".into(),
+                        profile: "release".into(),
+                        ..Environment::default()
+                    },
+                    ..RunContext::default()
+                },
+                part_1: None,
+                part_2: None,
+                total_nanos: 0_f64,
+            });
+        }
+
+        timings
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
+    }
+
+    #[test]
+    fn formats_marker_errors() {
+        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
+        let e = update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default())
+            .unwrap_err()
+            .in_file("README.md");
+        assert_eq!(
+            e.to_string(),
+            "README.md: found 3 occurrences of marker `<!--- benchmarking table --->`, expected at most two."
+        );
+    }
+
+    #[test]
+    fn updates_named_tables() {
+        let config = |name: &str| TableConfig {
+            name: Some(name.into()),
+            ..TableConfig::default()
+        };
+
+        let mut s = [
+            "<!--- benchmarking table 2023 --->",
+            "<!--- benchmarking table 2023 --->",
+            "<!--- benchmarking table 2024 --->",
+            "<!--- benchmarking table 2024 --->",
+        ]
+        .join("\n");
+
+        update_content(&mut s, get_mock_timings(), 190.0, &config("2023")).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config("2024")).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config("2024")).unwrap();
+
+        assert_eq!(s.matches("## Benchmarks (2023)").count(), 1);
+        assert_eq!(s.matches("## Benchmarks (2024)").count(), 1);
+        assert_eq!(s.matches("<!--- benchmarking table 2024 --->").count(), 2);
+        assert!(!s.contains(MARKER));
+    }
+
+    #[test]
+    fn links_chart_relative_to_file() {
+        assert_eq!(
+            relative_link("README.md", "data/benchmarks.svg"),
+            "./data/benchmarks.svg"
+        );
+        assert_eq!(
+            relative_link("docs/BENCHMARKS.md", "./data/benchmarks.svg"),
+            "../data/benchmarks.svg"
+        );
+    }
+
+    #[test]
+    fn ignores_markers_in_code() {
+        let mut s = format!(
+            "foo {MARKER}{MARKER}\nThe table goes between two `{MARKER}` markers:\n\n```md\n{MARKER}\n{MARKER}\n```\nkeep me\n"
+        );
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
+
+        assert!(s.starts_with(&format!("foo {MARKER}\n## Benchmarks")));
+        assert!(s.ends_with(&format!(
+            "The table goes between two `{MARKER}` markers:\n\n```md\n{MARKER}\n{MARKER}\n```\nkeep me\n"
+        )));
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn formats_environment_footer() {
+        let timings = with_environments(get_mock_timings(), &["Apple M1", "Apple M1", "Apple M1"]);
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+        assert!(s.contains(
+            "**Total: 190.00ms**\n\n_Measured on Apple M1, linux x86_64, `release` profile._\n<!--- benchmarking table --->"
+        ));
+    }
+
+    #[test]
+    fn formats_mixed_environment_footer() {
+        let timings = with_environments(get_mock_timings(), &["Apple M1", "Apple M2", "Apple M1"]);
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+        assert!(s.contains("_Measured on:_"));
+        assert!(s.contains("- Days 1, 4: Apple M1, linux x86_64, `release` profile"));
+        assert!(s.contains("- Day 2: Apple M2, linux x86_64, `release` profile"));
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,10 +786,11 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
+            "| **Total** | `80.0ms` | `110.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +799,101 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_configured_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].peak_memory_bytes = Some(3 * 1024 * 1024 / 2);
+
+        let config = TableConfig {
+            columns: vec![Column::Day, Column::Part2, Column::Memory, Column::Share],
+            sort: Sort::Slowest,
+            ..TableConfig::default()
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &config).unwrap();
+        assert!(
+            s.contains(
+                [
+                    "| Day | Part 2 | Memory | Share |",
+                    "| :---: | :---: | :---: | :---: |",
+                    "| [Day 4](./src/bin/04.rs) | `50.0ms` | - | 47.4% |",
+                    "| [Day 2](./src/bin/02.rs) | `40.0ms` | - | 36.8% |",
+                    "| [Day 1](./src/bin/01.rs) | `20.0ms` | 1.5 MiB | 15.8% |",
+                    "| **Total** | `110.0ms` |  | 100% |",
+                ]
+                .join("\n")
+                .as_str()
+            )
+        );
+    }
+
+    #[test]
+    fn formats_cold_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().cold_nanos = Some(25_000_000_f64);
+        timings.data[2].part_1.as_mut().unwrap().cold_nanos = Some(45_000_000_f64);
+
+        let config = TableConfig {
+            columns: vec![Column::Day, Column::Part1, Column::Cold1, Column::Cold2],
+            ..TableConfig::default()
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &config).unwrap();
+        assert!(
+            s.contains(
+                [
+                    "| Day | Part 1 | Part 1 (cold) | Part 2 (cold) |",
+                    "| :---: | :---: | :---: | :---: |",
+                    "| [Day 1](./src/bin/01.rs) | `10.0ms` | `25.0ms` | - |",
+                    "| [Day 2](./src/bin/02.rs) | `30.0ms` | - | - |",
+                    "| [Day 4](./src/bin/04.rs) | `40.0ms` | `45.0ms` | - |",
+                    "| **Total** | `80.0ms` | `70.0ms` | - |",
+                ]
+                .join("\n")
+                .as_str()
+            )
+        );
+    }
+
+    #[test]
+    fn mentions_merge_policy() {
+        let timings = Timings {
+            policy: MergePolicy::Best,
+            ..get_mock_timings()
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+        assert!(s.contains(
+            "**Total: 190.00ms**\n\n_Timings are the fastest measurement per part (`best` policy)._\n"
+        ));
+
+        let timings = Timings {
+            policy: MergePolicy::Mean,
+            ..get_mock_timings()
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+        assert!(s.contains(&format!(
+            "_Timings are the mean of up to the last {MAX_HISTORY_ENTRIES} measurements per part (`mean` policy)._"
+        )));
+    }
+
+    #[test]
+    fn references_benchmark_chart() {
+        let config = TableConfig {
+            chart: Some("./data/benchmarks.svg".into()),
+            ..TableConfig::default()
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &config).unwrap();
+        assert!(s.contains(
+            "**Total: 190.00ms**\n\n![Benchmark chart](./data/benchmarks.svg)\n<!--- benchmarking table --->"
+        ));
+    }
 }
@@ -0,0 +1,234 @@
+/// Module that updates the readme with a calendar of earned stars.
+/// It replaces the `advent-readme-stars` action, using the answers in downloaded puzzle descriptions instead of a
+/// private leaderboard.
+use crate::template::answers::KnownAnswers;
+use crate::template::readme_benchmarks::{Error, read_target, replace_section};
+use crate::template::timings::Timings;
+use crate::template::{Day, all_days, aoc_cli, config};
+use std::fs;
+
+/// Same marker as the one used by `advent-readme-stars`, so existing readmes keep working.
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+/// Shown instead of a star for parts of benched days whose answer was not accepted yet.
+static LOCAL_MARK: &str = "✓";
+
+/// Progress of a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+    /// Whether both parts are benched, see [`Timings::is_day_complete`]. Benched answers are not necessarily correct,
+    /// so they do not earn stars.
+    pub is_benched: bool,
+}
+
+impl DayProgress {
+    /// Read the progress of a day from its puzzle description and the stored timings.
+    fn read(day: Day, timings: &Timings) -> Self {
+        DayProgress {
+            is_benched: timings.is_day_complete(day),
+            ..Self::from_answers(day, &KnownAnswers::read(day))
+        }
+    }
+
+    /// A part is solved once its answer was accepted.
+    fn from_answers(day: Day, answers: &KnownAnswers) -> Self {
+        DayProgress {
+            day,
+            part_1: answers.part_1.is_some(),
+            part_2: answers.part_2.is_some(),
+            is_benched: false,
+        }
+    }
+
+    /// Whether a part was solved locally, but its answer was not accepted yet.
+    fn is_unsubmitted(self, part: u8) -> bool {
+        let is_solved = if part == 1 { self.part_1 } else { self.part_2 };
+        self.is_benched && !is_solved
+    }
+
+    fn stars(self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+}
+
+/// Collect the progress of every day with at least one star or stored timings for both parts.
+pub fn collect(timings: &Timings) -> Vec<DayProgress> {
+    all_days()
+        .map(|day| DayProgress::read(day, timings))
+        .filter(|progress| progress.stars() > 0 || progress.is_benched)
+        .collect()
+}
+
+fn construct_calendar(progress: &[DayProgress], year: Option<u16>) -> String {
+    let cell = |day: &DayProgress, part: u8| {
+        let is_solved = if part == 1 { day.part_1 } else { day.part_2 };
+
+        if is_solved {
+            "⭐"
+        } else if day.is_unsubmitted(part) {
+            LOCAL_MARK
+        } else {
+            " "
+        }
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        match year {
+            Some(year) => format!("## {year} Results"),
+            None => "## Results".into(),
+        },
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in progress {
+        let label = format!("Day {}", day.day.into_inner());
+        let label = match year {
+            Some(year) => format!(
+                "[{label}](https://adventofcode.com/{year}/day/{})",
+                day.day.into_inner()
+            ),
+            None => label,
+        };
+
+        lines.push(format!("| {label} | {} | {} |", cell(day, 1), cell(day, 2)));
+    }
+
+    let stars: usize = progress.iter().map(|day| day.stars()).sum();
+    lines.push(String::new());
+    lines.push(format!("**Stars: {stars}**"));
+
+    if progress
+        .iter()
+        .any(|day| day.is_unsubmitted(1) || day.is_unsubmitted(2))
+    {
+        lines.push(String::new());
+        lines.push(format!(
+            "_{LOCAL_MARK}: solved and benched locally, but not submitted yet._"
+        ));
+    }
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    progress: &[DayProgress],
+    year: Option<u16>,
+) -> Result<(), Error> {
+    replace_section(s, MARKER, &construct_calendar(progress, year))
+}
+
+/// Render the calendar into the readme. Returns the number of stars.
+pub fn update(timings: &Timings) -> Result<usize, Error> {
+    let progress = collect(timings);
+    let path = &config::get().readme;
+    let mut readme = read_target(path, MARKER).map_err(|e| e.in_file(path))?;
+
+    update_content(&mut readme, &progress, aoc_cli::get_year()).map_err(|e| e.in_file(path))?;
+    fs::write(path, &readme).map_err(|e| Error::from(e).in_file(path))?;
+
+    Ok(progress.iter().map(|day| day.stars()).sum())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayProgress, MARKER, update_content};
+    use crate::{day, template::answers::KnownAnswers};
+
+    fn get_mock_progress() -> Vec<DayProgress> {
+        vec![
+            DayProgress {
+                day: day!(1),
+                part_1: true,
+                part_2: true,
+                is_benched: true,
+            },
+            DayProgress {
+                day: day!(3),
+                part_1: true,
+                part_2: false,
+                is_benched: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn formats_calendar() {
+        let mut s = format!("foo\n{MARKER}\nbar");
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | ⭐ |   |",
+            "",
+            "**Stars: 3**",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn marks_unsubmitted_parts() {
+        let mut progress = get_mock_progress();
+        progress.push(DayProgress {
+            day: day!(4),
+            part_1: true,
+            part_2: false,
+            is_benched: true,
+        });
+
+        let mut s = MARKER.to_string();
+        update_content(&mut s, &progress, None).unwrap();
+        assert!(s.contains("| Day 1 | ⭐ | ⭐ |\n"));
+        assert!(s.contains("| Day 3 | ⭐ |   |\n"));
+        assert!(s.contains("| Day 4 | ⭐ | ✓ |\n"));
+        assert!(
+            s.contains("**Stars: 4**\n\n_✓: solved and benched locally, but not submitted yet._\n")
+        );
+    }
+
+    #[test]
+    fn counts_only_accepted_answers() {
+        let not_downloaded = DayProgress::from_answers(day!(4), &KnownAnswers::default());
+        assert_eq!(not_downloaded.stars(), 0);
+
+        let benched = DayProgress {
+            is_benched: true,
+            ..not_downloaded
+        };
+        assert_eq!(benched.stars(), 0);
+        assert!(benched.is_unsubmitted(1) && benched.is_unsubmitted(2));
+
+        let half_solved = DayProgress::from_answers(
+            day!(4),
+            &KnownAnswers {
+                part_1: Some("42".into()),
+                part_2: None,
+            },
+        );
+        assert_eq!(half_solved.stars(), 1);
+    }
+
+    #[test]
+    fn replaces_existing_calendar() {
+        let mut s = format!("{MARKER}\n## 2024 Results\n{MARKER}\n<!--- benchmarking table --->");
+        update_content(&mut s, &get_mock_progress(), None).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.contains("## Results\n"));
+        assert!(!s.contains("2024"));
+        assert!(s.ends_with("<!--- benchmarking table --->"));
+    }
+}
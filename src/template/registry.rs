@@ -0,0 +1,27 @@
+/// In-process solution registry.
+/// With the `registry` feature enabled, the build script compiles every scaffolded solution in `src/bin` into the library as well.
+/// This allows `all` and `time` to run every day in a single process instead of spawning one `cargo run` per day.
+use crate::template::Day;
+use crate::template::runner::PartResult;
+
+/// A solution part, wrapped so it can be invoked without knowing its answer type.
+pub struct SolutionPart {
+    pub part: u8,
+    pub run: fn(&str, bool) -> PartResult,
+}
+
+/// A registered solution for a single day.
+pub struct Solution {
+    pub day: Day,
+    /// The input parser, if the solution declares one.
+    pub parse: Option<fn(&str, bool) -> PartResult>,
+    pub parts: &'static [SolutionPart],
+}
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// Look up the registered solution for a given day.
+#[must_use]
+pub fn find(day: Day) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| solution.day == day)
+}
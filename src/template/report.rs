@@ -0,0 +1,411 @@
+/// Module that writes machine-readable reports of `all` and `time` runs, e.g. for CI dashboards.
+/// Reports contain one entry per day and part with its answer, duration, status and, if known, whether the answer is correct.
+use std::{collections::HashMap, fmt::Write, fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day,
+    answers::KnownAnswers,
+    run_multi::{DayStatus, Outcome},
+    timings::optional_string,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Junit,
+    Json,
+}
+
+/// A report that should be written after a run. Parsed from `<format>=<path>`, e.g. `junit=report.xml`.
+#[derive(Clone, Debug)]
+pub struct ReportTarget {
+    pub format: ReportFormat,
+    pub path: PathBuf,
+}
+
+impl FromStr for ReportTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (format, path) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected report as `<format>=<path>`, got \"{s}\"."))?;
+
+        let format = match format {
+            "junit" => ReportFormat::Junit,
+            "json" => ReportFormat::Json,
+            x => {
+                return Err(format!(
+                    "unknown report format \"{x}\", expected `junit` or `json`."
+                ));
+            }
+        };
+
+        if path.is_empty() {
+            return Err(format!("expected a path for the {s} report."));
+        }
+
+        Ok(ReportTarget {
+            format,
+            path: PathBuf::from(path),
+        })
+    }
+}
+
+/// The outcome of a single part, derived from the day status and the known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CaseOutcome {
+    Passed,
+    Failure(String),
+    Error(String),
+    Skipped(String),
+}
+
+impl CaseOutcome {
+    fn id(&self) -> &'static str {
+        match self {
+            CaseOutcome::Passed => "passed",
+            CaseOutcome::Failure(_) => "failure",
+            CaseOutcome::Error(_) => "error",
+            CaseOutcome::Skipped(_) => "skipped",
+        }
+    }
+}
+
+/// The result of a single part, as it appears in reports.
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub duration_nanos: Option<f64>,
+    pub samples: u128,
+}
+
+impl PartReport {
+    /// Whether the answer matches the known answer. `None` if either of them is missing.
+    pub fn is_correct(&self) -> Option<bool> {
+        Some(self.answer.as_ref()? == self.expected.as_ref()?)
+    }
+}
+
+/// The result of a single day, as it appears in reports.
+pub struct DayReport {
+    pub day: Day,
+    pub status: DayStatus,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    fn case_outcome(&self, part: &PartReport) -> CaseOutcome {
+        match (self.status, &part.answer) {
+            (DayStatus::NotScaffolded, _) => CaseOutcome::Skipped("not scaffolded".into()),
+            (DayStatus::CompileError, _) => CaseOutcome::Error("failed to compile".into()),
+            (DayStatus::Failed, None) => CaseOutcome::Failure("solution failed".into()),
+            (DayStatus::TimedOut, None) => CaseOutcome::Failure("solution timed out".into()),
+            (DayStatus::Solved, None) => CaseOutcome::Skipped("not solved".into()),
+            (_, Some(answer)) => match (part.is_correct(), &part.expected) {
+                (Some(false), Some(expected)) => {
+                    CaseOutcome::Failure(format!("expected {expected}, got {answer}"))
+                }
+                _ => CaseOutcome::Passed,
+            },
+        }
+    }
+}
+
+/// A serializable model of an `all` or `time` run.
+pub struct Report {
+    pub days: Vec<DayReport>,
+}
+
+impl Report {
+    pub fn from_outcome(outcome: &Outcome) -> Self {
+        let days = outcome
+            .days
+            .iter()
+            .map(|(day, result)| {
+                let known_answers = KnownAnswers::read(*day);
+
+                let parts = (1..=2)
+                    .map(|part| {
+                        let result = result
+                            .parts
+                            .iter()
+                            .find(|(p, _)| *p == part)
+                            .map(|(_, result)| result);
+
+                        PartReport {
+                            part,
+                            answer: result.and_then(|r| r.answer.clone()),
+                            expected: known_answers.get(part).cloned(),
+                            duration_nanos: result
+                                .filter(|r| r.answer.is_some())
+                                .map(|r| r.duration.as_nanos() as f64),
+                            samples: result.map_or(0, |r| r.samples),
+                        }
+                    })
+                    .collect();
+
+                DayReport {
+                    day: *day,
+                    status: result.status,
+                    parts,
+                }
+            })
+            .collect();
+
+        Report { days }
+    }
+
+    /// Write the report in the requested format.
+    pub fn write(&self, target: &ReportTarget) -> Result<(), io::Error> {
+        let contents = match target.format {
+            ReportFormat::Junit => self.to_junit(),
+            ReportFormat::Json => JsonValue::from(self)
+                .format()
+                .map_err(|e| io::Error::other(e.to_string()))?,
+        };
+
+        if let Some(parent) = target.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&target.path, contents)
+    }
+
+    fn to_junit(&self) -> String {
+        let cases: Vec<(&DayReport, &PartReport, CaseOutcome)> = self
+            .days
+            .iter()
+            .flat_map(|day| {
+                day.parts
+                    .iter()
+                    .map(move |part| (day, part, day.case_outcome(part)))
+            })
+            .collect();
+
+        let count = |id: &str| cases.iter().filter(|(_, _, o)| o.id() == id).count();
+
+        let total_secs = cases
+            .iter()
+            .filter_map(|(_, part, _)| part.duration_nanos)
+            .sum::<f64>()
+            / 1_000_000_000_f64;
+
+        let attributes = format!(
+            "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{total_secs:.9}\"",
+            cases.len(),
+            count("failure"),
+            count("error"),
+            count("skipped"),
+        );
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(xml, "<testsuites name=\"advent_of_code\" {attributes}>");
+        let _ = writeln!(xml, "  <testsuite name=\"advent_of_code\" {attributes}>");
+
+        for (day, part, outcome) in &cases {
+            let secs = part.duration_nanos.unwrap_or_default() / 1_000_000_000_f64;
+            let _ = writeln!(
+                xml,
+                "    <testcase classname=\"Day {}\" name=\"Part {}\" time=\"{secs:.9}\">",
+                day.day, part.part
+            );
+
+            match outcome {
+                CaseOutcome::Passed => {}
+                CaseOutcome::Failure(message) => {
+                    let _ = writeln!(xml, "      <failure message=\"{}\"/>", escape_xml(message));
+                }
+                CaseOutcome::Error(message) => {
+                    let _ = writeln!(xml, "      <error message=\"{}\"/>", escape_xml(message));
+                }
+                CaseOutcome::Skipped(message) => {
+                    let _ = writeln!(xml, "      <skipped message=\"{}\"/>", escape_xml(message));
+                }
+            }
+
+            if let Some(answer) = &part.answer {
+                let _ = writeln!(xml, "      <system-out>{}</system-out>", escape_xml(answer));
+            }
+
+            xml.push_str("    </testcase>\n");
+        }
+
+        xml.push_str("  </testsuite>\n");
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+/// Write all requested reports for a run.
+pub fn write_reports(outcome: &Outcome, targets: &[ReportTarget]) -> Result<(), io::Error> {
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    let report = Report::from_outcome(outcome);
+
+    for target in targets {
+        report.write(target)?;
+        println!("Wrote report to \"{}\".", target.path.display());
+    }
+
+    Ok(())
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Report> for JsonValue {
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("version".into(), JsonValue::Number(1_f64));
+        map.insert(
+            "days".into(),
+            JsonValue::Array(value.days.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&DayReport> for JsonValue {
+    fn from(value: &DayReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("status".into(), JsonValue::String(value.status.id().into()));
+
+        let parts = value
+            .parts
+            .iter()
+            .map(|part| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+                map.insert("part".into(), JsonValue::Number(f64::from(part.part)));
+                map.insert("answer".into(), optional_string(part.answer.as_ref()));
+                map.insert("expected".into(), optional_string(part.expected.as_ref()));
+                map.insert(
+                    "correct".into(),
+                    part.is_correct()
+                        .map_or(JsonValue::Null, JsonValue::Boolean),
+                );
+                map.insert(
+                    "duration_nanos".into(),
+                    part.duration_nanos
+                        .map_or(JsonValue::Null, JsonValue::Number),
+                );
+                map.insert("samples".into(), JsonValue::Number(part.samples as f64));
+                map.insert(
+                    "outcome".into(),
+                    JsonValue::String(value.case_outcome(part).id().into()),
+                );
+
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        map.insert("parts".into(), JsonValue::Array(parts));
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayReport, PartReport, Report, ReportFormat, ReportTarget};
+    use crate::{day, template::run_multi::DayStatus};
+    use std::{collections::HashMap, str::FromStr};
+    use tinyjson::JsonValue;
+
+    fn part(part: u8, answer: Option<&str>, expected: Option<&str>) -> PartReport {
+        PartReport {
+            part,
+            answer: answer.map(str::to_string),
+            expected: expected.map(str::to_string),
+            duration_nanos: answer.map(|_| 1_500_000_f64),
+            samples: 10,
+        }
+    }
+
+    fn get_mock_report() -> Report {
+        Report {
+            days: vec![
+                DayReport {
+                    day: day!(1),
+                    status: DayStatus::Solved,
+                    parts: vec![
+                        part(1, Some("42"), Some("42")),
+                        part(2, Some("7"), Some("8")),
+                    ],
+                },
+                DayReport {
+                    day: day!(2),
+                    status: DayStatus::Failed,
+                    parts: vec![part(1, Some("<&>"), None), part(2, None, None)],
+                },
+                DayReport {
+                    day: day!(3),
+                    status: DayStatus::NotScaffolded,
+                    parts: vec![part(1, None, None), part(2, None, None)],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_report_targets() {
+        let target = ReportTarget::from_str("junit=out/report.xml").unwrap();
+        assert_eq!(target.format, ReportFormat::Junit);
+        assert_eq!(target.path.to_str(), Some("out/report.xml"));
+
+        assert!(ReportTarget::from_str("json=").is_err());
+        assert!(ReportTarget::from_str("yaml=report.yml").is_err());
+        assert!(ReportTarget::from_str("report.json").is_err());
+    }
+
+    #[test]
+    fn writes_junit_report() {
+        let xml = get_mock_report().to_junit();
+        assert!(xml.contains(
+            "<testsuites name=\"advent_of_code\" tests=\"6\" failures=\"2\" errors=\"0\" skipped=\"2\""
+        ));
+        assert!(
+            xml.contains("<testcase classname=\"Day 01\" name=\"Part 1\" time=\"0.001500000\">")
+        );
+        assert!(xml.contains("<failure message=\"expected 8, got 7\"/>"));
+        assert!(xml.contains("<failure message=\"solution failed\"/>"));
+        assert!(xml.contains("<system-out>&lt;&amp;&gt;</system-out>"));
+        assert!(xml.contains("<skipped message=\"not scaffolded\"/>"));
+    }
+
+    #[test]
+    fn writes_json_report() {
+        let json = JsonValue::from(&get_mock_report());
+        let days = json["days"].get::<Vec<JsonValue>>().unwrap();
+        assert_eq!(days.len(), 3);
+
+        let day = days[0].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(day["status"].get::<String>().unwrap(), "solved");
+
+        let parts = day["parts"].get::<Vec<JsonValue>>().unwrap();
+        assert_eq!(parts[0]["correct"].get::<bool>(), Some(&true));
+        assert_eq!(parts[1]["correct"].get::<bool>(), Some(&false));
+        assert_eq!(parts[1]["outcome"].get::<String>().unwrap(), "failure");
+        assert_eq!(
+            parts[1]["duration_nanos"].get::<f64>(),
+            Some(&1_500_000_f64)
+        );
+    }
+}
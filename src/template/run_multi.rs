@@ -1,55 +1,419 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    process::ExitStatus,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Pinning, ansi, profiles::BuildProfile,
+    runner::PartResult,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// A line of output produced while running a solution.
+pub enum OutputLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl OutputLine {
+    fn print(&self) {
+        match self {
+            OutputLine::Stdout(line) => println!("{line}"),
+            OutputLine::Stderr(line) => eprintln!("{line}"),
+        }
+    }
+}
+
+/// Options that control how a set of days is run.
+pub struct RunOptions {
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+    /// CPU core and priority of the solutions, see [`Pinning`].
+    pub pinning: Pinning,
+    /// Cargo profile to build solutions with. Defaults to `release` or `dev`, depending on `is_release`.
+    pub profile: Option<BuildProfile>,
+}
+
+/// The final status of a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    Solved,
+    Failed,
+    CompileError,
+    NotScaffolded,
+    TimedOut,
+}
+
+impl DayStatus {
+    pub const ALL: [DayStatus; 5] = [
+        DayStatus::Solved,
+        DayStatus::Failed,
+        DayStatus::CompileError,
+        DayStatus::NotScaffolded,
+        DayStatus::TimedOut,
+    ];
+
+    /// Whether this status should fail the run.
+    pub fn is_failure(self) -> bool {
+        matches!(
+            self,
+            DayStatus::Failed | DayStatus::CompileError | DayStatus::TimedOut
+        )
+    }
+
+    /// A stable identifier of this status, used in reports.
+    pub fn id(self) -> &'static str {
+        match self {
+            DayStatus::Solved => "solved",
+            DayStatus::Failed => "failed",
+            DayStatus::CompileError => "compile_error",
+            DayStatus::NotScaffolded => "not_scaffolded",
+            DayStatus::TimedOut => "timed_out",
+        }
+    }
+}
+
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DayStatus::Solved => "Solved",
+            DayStatus::Failed => "Failed",
+            DayStatus::CompileError => "Compile error",
+            DayStatus::NotScaffolded => "Not scaffolded",
+            DayStatus::TimedOut => "Timed out",
+        })
+    }
+}
+
+/// The result of running a single day.
+/// Parts contain the answers that were printed before the solution finished, timings are only present for solved days.
+pub struct DayResult {
+    pub status: DayStatus,
+    pub parts: Vec<(u8, PartResult)>,
+    pub timing: Option<Timing>,
+}
+
+impl DayResult {
+    fn from_status(status: DayStatus) -> Self {
+        DayResult {
+            status,
+            parts: vec![],
+            timing: None,
+        }
+    }
+}
+
+/// The outcome of running a set of days.
+pub struct Outcome {
+    pub days: Vec<(Day, DayResult)>,
+    pub timings: Timings,
+}
+
+impl Outcome {
+    /// Whether every day that was run finished without failure.
+    pub fn is_success(&self) -> bool {
+        self.days
+            .iter()
+            .all(|(_, result)| !result.status.is_failure())
+    }
+
+    fn print_summary(&self) {
+        println!("\n{}Summary{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
+        println!("-------");
+
+        for line in self.summary() {
+            println!("{line}");
+        }
+    }
+
+    /// One line per status with the number of days that ended with it, e.g. `Failed:          2 (03, 07)`.
+    fn summary(&self) -> Vec<String> {
+        DayStatus::ALL
+            .into_iter()
+            .map(|status| {
+                let days: Vec<String> = self
+                    .days
+                    .iter()
+                    .filter(|(_, result)| result.status == status)
+                    .map(|(day, _)| day.to_string())
+                    .collect();
+
+                if days.is_empty() {
+                    format!("{:<16}{:>2}", format!("{status}:"), 0)
+                } else {
+                    format!(
+                        "{:<16}{:>2} ({})",
+                        format!("{status}:"),
+                        days.len(),
+                        days.join(", ")
+                    )
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn run_multi(days_to_run: &HashSet<Day>, options: &RunOptions) -> Result<Outcome, Error> {
+    run_checkout(None, days_to_run, options)
+}
+
+/// Run the solutions of another checkout of this repository, e.g. a git worktree of an older revision.
+/// These solutions are always built and run as binaries. They run in the current directory, so they read the same inputs.
+pub fn run_multi_in(
+    checkout: &Path,
+    days_to_run: &HashSet<Day>,
+    options: &RunOptions,
+) -> Result<Outcome, Error> {
+    run_checkout(Some(checkout), days_to_run, options)
+}
+
+fn run_checkout(
+    checkout: Option<&Path>,
+    days_to_run: &HashSet<Day>,
+    options: &RunOptions,
+) -> Result<Outcome, Error> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut results: Vec<(Day, DayResult)> = Vec::with_capacity(days_to_run.len());
+    let mut error = None;
+
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    // build all binaries we need up front, so cargo is only invoked once.
+    let days_to_build: Vec<Day> = days
+        .iter()
+        .copied()
+        .filter(|day| needs_binary(*day, checkout, options))
+        .collect();
+
+    let executables = if days_to_build.is_empty() {
+        HashMap::new()
+    } else {
+        child_commands::build_solutions(&days_to_build, options, checkout)?
+    };
+
+    let build_failures = days_to_build
+        .iter()
+        .filter(|day| !executables.contains_key(day))
+        .count();
+
+    if build_failures > 0 {
+        eprintln!("Failed to build {build_failures} solution(s), see the compiler output above.\n");
+    }
 
     let mut need_space = false;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
+    run_days(
+        &days,
+        options.jobs,
+        |day, emit| run_day(day, checkout, executables.get(&day), options, emit),
+        |day| {
             if need_space {
                 println!();
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("{}Day {day}{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
             println!("------");
+        },
+        |day, result| match result {
+            Ok(mut result) => {
+                match result.status {
+                    DayStatus::Solved => {}
+                    DayStatus::Failed => println!("Failed."),
+                    DayStatus::CompileError => println!("Failed to compile."),
+                    DayStatus::NotScaffolded => println!("Not scaffolded."),
+                    DayStatus::TimedOut => println!("Timed out."),
+                }
+                timings.extend(result.timing.take());
+                results.push((day, result));
+            }
+            Err(e) => {
+                eprintln!("Error: {e}");
+                error.get_or_insert(e);
+            }
+        },
+    );
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    if let Some(e) = error {
+        return Err(e);
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    let outcome = Outcome {
+        days: results,
+        timings: Timings {
+            data: timings,
+            ..Timings::default()
+        },
+    };
+
+    if options.is_timed {
+        let total_millis = outcome.timings.total_millis();
+        let (bold, italic, reset) = (ansi(ANSI_BOLD), ansi(ANSI_ITALIC), ansi(ANSI_RESET));
+        println!("\n{bold}Total (Run):{reset} {italic}{total_millis:.2}ms{reset}");
+    }
+
+    outcome.print_summary();
+
+    Ok(outcome)
+}
+
+/// Run `run` for every day, using up to `jobs` threads.
+/// Output is streamed while running sequentially. When running in parallel, each day's output is buffered
+/// and printed in day order once all previous days have finished.
+fn run_days<R: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day, &mut dyn FnMut(OutputLine)) -> R + Sync,
+    mut on_start: impl FnMut(Day),
+    mut on_finish: impl FnMut(Day, R),
+) {
+    if jobs <= 1 {
+        for &day in days {
+            on_start(day);
+            let result = run(day, &mut |line| line.print());
+            on_finish(day, result);
+        }
+        return;
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next_index = &next_index;
+            let run = &run;
+
+            scope.spawn(move || {
+                loop {
+                    let index = next_index.fetch_add(1, Ordering::Relaxed);
+                    let Some(&day) = days.get(index) else {
+                        break;
+                    };
+
+                    let mut output = vec![];
+                    let result = run(day, &mut |line| output.push(line));
+
+                    if tx.send((index, output, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut finished = HashMap::new();
+        let mut next_to_print = 0;
+
+        for (index, output, result) in rx {
+            finished.insert(index, (output, result));
+
+            while let Some((output, result)) = finished.remove(&next_to_print) {
+                let day = days[next_to_print];
+                on_start(day);
+                output.iter().for_each(OutputLine::print);
+                on_finish(day, result);
+                next_to_print += 1;
             }
-        });
+        }
+    });
+}
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+/// Whether a day has to be run via its binary, i.e. it is scaffolded but not part of the in-process registry.
+/// Other checkouts and build profiles are always run via their binaries.
+#[cfg_attr(not(feature = "registry"), allow(unused_variables))]
+fn needs_binary(day: Day, checkout: Option<&Path>, options: &RunOptions) -> bool {
+    #[cfg(feature = "registry")]
+    if checkout.is_none()
+        && options.profile.is_none()
+        && crate::template::registry::find(day).is_some()
+    {
+        return false;
+    }
+
+    is_scaffolded(day, checkout)
+}
+
+fn is_scaffolded(day: Day, checkout: Option<&Path>) -> bool {
+    checkout
+        .unwrap_or(Path::new("."))
+        .join(get_path_for_bin(day))
+        .exists()
+}
+
+fn run_day(
+    day: Day,
+    checkout: Option<&Path>,
+    executable: Option<&PathBuf>,
+    options: &RunOptions,
+    emit: &mut dyn FnMut(OutputLine),
+) -> Result<DayResult, Error> {
+    // NOTE: solutions missing from the registry (e.g. scaffolded after the last build) fall back to the binary.
+    #[cfg(feature = "registry")]
+    if checkout.is_none()
+        && options.profile.is_none()
+        && crate::template::registry::find(day).is_some()
+    {
+        // NOTE: in-process days run on the calling thread. It is only pinned now, after all solutions were built,
+        // so compiling is not restricted to a single core.
+        if let Err(e) = options.pinning.pin_current_thread() {
+            emit(OutputLine::Stderr(format!(
+                "Warning: failed to pin to a CPU: {e}"
+            )));
+        }
+        return Ok(in_process::run_solution(day, options.is_timed, emit));
+    }
+
+    if !is_scaffolded(day, checkout) {
+        return Ok(DayResult::from_status(DayStatus::NotScaffolded));
+    }
+
+    let Some(executable) = executable else {
+        return Ok(DayResult::from_status(DayStatus::CompileError));
+    };
+
+    let output = child_commands::run_solution(executable, options, emit)?;
+    let parts = child_commands::parse_parts(&output.stdout);
+
+    if output.status != DayStatus::Solved {
+        return Ok(DayResult {
+            status: output.status,
+            parts,
+            timing: None,
+        });
     }
+
+    Ok(DayResult {
+        status: DayStatus::Solved,
+        parts,
+        timing: Some(child_commands::parse_exec_time(&output.stdout, day)),
+    })
 }
 
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    /// Cargo failed without building any solution, e.g. because the library does not compile.
+    BuildFailed(ExitStatus),
     IO(io::Error),
 }
 
@@ -59,47 +423,274 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "lost connection to a solution's output."),
+            Error::BuildFailed(status) => write!(
+                f,
+                "failed to build the solutions, cargo exited with {status}. See the output above."
+            ),
+            Error::IO(e) => write!(f, "failed to run solutions: {e}"),
+        }
+    }
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// With the `registry` feature, solutions are compiled into the library and invoked directly.
+/// Output mirrors what the solution binaries print, but timings are taken from the measurements instead of parsed.
+#[cfg(feature = "registry")]
+pub mod in_process {
+    use super::{DayResult, DayStatus, OutputLine, Timing};
+    use crate::template::{
+        Day, aoc_cli, read_file, registry,
+        runner::{format_duration, format_result},
+        timings::PartTiming,
+    };
+    use std::{panic, path::Path};
+
+    /// Run the registered solution for a given day.
+    /// Panics are caught and reported as failures. Timeouts can not be enforced in-process.
+    pub fn run_solution(day: Day, is_timed: bool, emit: &mut dyn FnMut(OutputLine)) -> DayResult {
+        let Some(solution) = registry::find(day) else {
+            return DayResult::from_status(DayStatus::NotScaffolded);
+        };
+
+        let input_path = aoc_cli::get_input_path(day);
+
+        if !Path::new(&input_path).exists() {
+            emit(OutputLine::Stderr(format!(
+                "Could not find input file \"{input_path}\"."
+            )));
+            return DayResult::from_status(DayStatus::Failed);
+        }
+
+        let input = read_file("inputs", day);
+
+        let mut timing = Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            parse: None,
+            total_nanos: 0_f64,
+            peak_memory_bytes: None,
+            history: vec![],
+        };
+
+        let mut parts = vec![];
+
+        if let Some(parse) = solution.parse {
+            let Ok(result) = panic::catch_unwind(|| parse(&input, is_timed)) else {
+                emit(OutputLine::Stderr("Parse panicked.".into()));
+                return DayResult::from_status(DayStatus::Failed);
+            };
+
+            emit(OutputLine::Stdout(format!(
+                "Parse:{}",
+                format_duration(&result)
+            )));
+
+            if is_timed {
+                timing.parse = Some(PartTiming::from(&result));
+            }
+        }
+
+        for part in solution.parts {
+            let Ok(result) = panic::catch_unwind(|| (part.run)(&input, is_timed)) else {
+                emit(OutputLine::Stderr(format!("Part {} panicked.", part.part)));
+                return DayResult {
+                    status: DayStatus::Failed,
+                    parts,
+                    timing: None,
+                };
+            };
+
+            emit(OutputLine::Stdout(format_result(
+                &result.answer,
+                &format!("Part {}", part.part),
+                &format_duration(&result),
+            )));
+
+            // NOTE: like solution binaries, only parts with an answer are timed.
+            if is_timed && result.answer.is_some() {
+                let part_timing = PartTiming::from(&result);
+                timing.total_nanos += part_timing.nanos;
+                match part.part {
+                    1 => timing.part_1 = Some(part_timing),
+                    _ => timing.part_2 = Some(part_timing),
+                }
+            }
+
+            parts.push((part.part, result));
+        }
+
+        DayResult {
+            status: DayStatus::Solved,
+            parts,
+            timing: Some(timing),
+        }
+    }
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{DayStatus, Error, OutputLine, RunOptions};
+    use crate::template::{
+        ANSI_BOLD, ANSI_RESET, Day,
+        config::{self, COLOR_ENV},
+        runner::{PartResult, STATS_ENV, STATS_PREFIX},
+        timings::{PartTiming, SampleStats, parse_nanos},
+    };
     use std::{
+        collections::HashMap,
+        env,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        str::FromStr,
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// Build the solution binaries for the given days with a single cargo invocation.
+    /// Cargo's progress and diagnostics are forwarded to stderr, the paths of the built executables are returned.
+    /// Days that failed to compile are missing from the result.
+    /// If `checkout` is given, the solutions of that checkout are built instead of the current directory's.
+    pub fn build_solutions(
+        days: &[Day],
+        options: &RunOptions,
+        checkout: Option<&Path>,
+    ) -> Result<HashMap<Day, PathBuf>, Error> {
+        let mut args = vec![
+            "build".to_string(),
+            "--keep-going".to_string(),
+            "--message-format=json-render-diagnostics".to_string(),
+        ];
+
+        if let Some(profile) = &options.profile {
+            args.push("--profile".into());
+            args.push(profile.profile.clone());
+        } else if options.is_release {
+            args.push("--release".into());
+        }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        for day in days {
+            args.push("--bin".into());
+            args.push(day.to_string());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut cmd = Command::new("cargo");
 
-        if is_release {
-            args.push("--release");
+        if let Some(profile) = &options.profile {
+            if let Some(rustflags) = profile.rustflags() {
+                cmd.env("RUSTFLAGS", rustflags);
+            }
+
+            if let Some(target_dir) = profile.target_dir() {
+                cmd.env("CARGO_TARGET_DIR", env::current_dir()?.join(target_dir));
+            }
+        }
+
+        if let Some(checkout) = checkout {
+            // NOTE: other checkouts get a target directory of their own, so they do not invalidate the main build.
+            let target_dir = env::current_dir()?.join("target").join("baseline");
+            cmd.current_dir(checkout)
+                .env("CARGO_TARGET_DIR", target_dir);
+        }
+
+        let mut cmd = cmd
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(Error::BrokenPipe)?);
+
+        let executables: HashMap<Day, PathBuf> = stdout
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| parse_executable(&line))
+            .collect();
+
+        let status = cmd.wait()?;
+
+        // NOTE: with `--keep-going`, cargo also fails if only some solutions do not compile. These are reported per day.
+        if !status.success() && executables.is_empty() {
+            return Err(Error::BuildFailed(status));
+        }
+
+        Ok(executables)
+    }
+
+    /// Extract the day and executable path from a `compiler-artifact` message of `cargo build --message-format=json`.
+    fn parse_executable(line: &str) -> Option<(Day, PathBuf)> {
+        let json = JsonValue::from_str(line).ok()?;
+        let message = json.get::<HashMap<String, JsonValue>>()?;
+
+        if message.get("reason")?.get::<String>()? != "compiler-artifact" {
+            return None;
         }
 
-        if is_timed {
+        let day = message
+            .get("target")?
+            .get::<HashMap<String, JsonValue>>()?
+            .get("name")?
+            .get::<String>()?
+            .parse()
+            .ok()?;
+
+        let executable = message.get("executable")?.get::<String>()?;
+
+        Some((day, PathBuf::from(executable)))
+    }
+
+    /// The stdout lines and final status of a solution binary.
+    pub struct ChildOutput {
+        pub stdout: Vec<String>,
+        pub status: DayStatus,
+    }
+
+    /// Run a built solution binary, passing its output to `emit` while grabbing stdout lines.
+    /// The binary is killed if it runs longer than the configured timeout.
+    pub fn run_solution(
+        executable: &Path,
+        options: &RunOptions,
+        emit: &mut dyn FnMut(OutputLine),
+    ) -> Result<ChildOutput, Error> {
+        let mut args = vec![];
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let mut cmd = Command::new(executable);
+
+        if options.is_timed {
+            cmd.env(STATS_ENV, "1");
+        }
+
+        // NOTE: output is piped, so solutions can not tell whether it ends up in a terminal.
+        cmd.env(
+            COLOR_ENV,
+            if config::get().use_color() {
+                "always"
+            } else {
+                "never"
+            },
+        );
 
-        let mut cmd = Command::new("cargo")
+        options.pinning.pass_to(&mut cmd);
+
+        let mut cmd = cmd
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,54 +699,161 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        // NOTE: both streams are funneled through one channel to keep the order in which lines were written.
+        let (tx, rx) = mpsc::channel();
+        let stderr_tx = tx.clone();
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        let stdout_thread = thread::spawn(move || {
+            stdout.lines().map_while(Result::ok).for_each(|line| {
+                let _ = tx.send(OutputLine::Stdout(line));
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let stderr_thread = thread::spawn(move || {
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                let _ = stderr_tx.send(OutputLine::Stderr(line));
+            });
+        });
+
+        let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+        let mut output = vec![];
+        let mut is_timed_out = false;
+
+        loop {
+            let line = match deadline {
+                Some(deadline) => {
+                    match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                        Ok(line) => line,
+                        Err(RecvTimeoutError::Timeout) => {
+                            is_timed_out = true;
+                            cmd.kill()?;
+                            break;
+                        }
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                None => match rx.recv() {
+                    Ok(line) => line,
+                    Err(_) => break,
+                },
+            };
+
+            if let OutputLine::Stdout(line) = &line {
+                output.push(line.clone());
+
+                // statistics are only meant for parsing, see `parse_exec_time`.
+                if line.starts_with(STATS_PREFIX) {
+                    continue;
+                }
+            }
+            emit(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        // NOTE: processes started by the solution may keep the pipes open after it was killed, so the readers of a
+        // timed out solution are detached instead of waited for. They finish once the pipes are closed.
+        if !is_timed_out {
+            stdout_thread.join().unwrap();
+            stderr_thread.join().unwrap();
+        }
+
+        let exit_status = cmd.wait()?;
 
-        Ok(output)
+        let status = if is_timed_out {
+            DayStatus::TimedOut
+        } else if exit_status.success() {
+            DayStatus::Solved
+        } else {
+            emit(OutputLine::Stderr(format!(
+                "Solution exited with {exit_status}."
+            )));
+            DayStatus::Failed
+        };
+
+        Ok(ChildOutput {
+            stdout: output,
+            status,
+        })
     }
 
+    /// Parse the timings of a benched solution binary.
+    /// Solutions print machine-readable statistics if asked to. Older solutions, e.g. of a baseline revision, do not,
+    /// in which case the timings are parsed from the human-readable output.
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            parse: None,
             total_nanos: 0_f64,
+            peak_memory_bytes: None,
+            history: vec![],
         };
 
+        timings.peak_memory_bytes = output.iter().find_map(|l| {
+            l.strip_prefix(STATS_PREFIX)?
+                .strip_prefix("peak_memory_bytes=")?
+                .trim()
+                .parse()
+                .ok()
+        });
+
+        // NOTE: solutions built from older revisions also report statistics for parts without an answer.
+        let unsolved: Vec<u8> = parse_parts(output)
+            .into_iter()
+            .filter(|(_, result)| result.answer.is_none())
+            .map(|(part, _)| part)
+            .collect();
+
+        let stats: Vec<(u8, PartTiming)> = output
+            .iter()
+            .filter_map(|l| parse_stats(l))
+            .filter(|(part, _)| !unsolved.contains(part))
+            .collect();
+
+        if !stats.is_empty() {
+            for (part, part_timing) in stats {
+                match part {
+                    // NOTE: the parser is reported as part 0 and not counted towards the total.
+                    0 => timings.parse = Some(part_timing),
+                    1 => {
+                        timings.total_nanos += part_timing.nanos;
+                        timings.part_1 = Some(part_timing);
+                    }
+                    _ => {
+                        timings.total_nanos += part_timing.nanos;
+                        timings.part_2 = Some(part_timing);
+                    }
+                }
+            }
+
+            return timings;
+        }
+
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                if !l.contains(" samples") {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some((_, nanos)) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, nanos))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, nanos)| {
                 if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+                    timings.part_1 = Some(PartTiming::from_nanos(nanos));
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2 = Some(PartTiming::from_nanos(nanos));
+                } else if part.contains("Parse") {
+                    timings.parse = Some(PartTiming::from_nanos(nanos));
+                    return;
+                } else {
+                    return;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,14 +862,118 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Parse a statistics line like `@aoc-stats part=1 mean_nanos=74 samples=10 min_nanos=70 max_nanos=80 stddev_nanos=3 cold_nanos=90`.
+    fn parse_stats(line: &str) -> Option<(u8, PartTiming)> {
+        let values: HashMap<&str, &str> = line
+            .strip_prefix(STATS_PREFIX)?
+            .split_whitespace()
+            .filter_map(|pair| pair.split_once('='))
+            .collect();
+
+        let number = |key: &str| values.get(key)?.parse::<f64>().ok();
+
+        Some((
+            values.get("part")?.parse().ok()?,
+            PartTiming {
+                nanos: number("mean_nanos")?,
+                stats: Some(SampleStats {
+                    samples: values.get("samples")?.parse().ok()?,
+                    min_nanos: number("min_nanos")?,
+                    max_nanos: number("max_nanos")?,
+                    stddev_nanos: number("stddev_nanos")?,
+                }),
+                cold_nanos: number("cold_nanos"),
+            },
+        ))
+    }
+
+    /// Parse the answers and timings of all parts from the output of a solution binary.
+    pub fn parse_parts(output: &[String]) -> Vec<(u8, PartResult)> {
+        // NOTE: intermediate results are overwritten with a carriage return, only the last segment is final.
+        let lines: Vec<&str> = output
+            .iter()
+            .map(|l| l.rsplit('\r').next().unwrap_or(l))
+            .collect();
+
+        lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let (part, rest) = line.strip_prefix("Part ")?.split_once(": ")?;
+                let part = part.parse().ok()?;
+
+                if rest.starts_with('✖') {
+                    return Some((part, PartResult::default()));
+                }
+
+                let (answer, timing_str) = if let Some(timing_str) = rest.strip_prefix("▼ ") {
+                    // multi-line answers are printed below the part line.
+                    let answer = lines[i + 1..]
+                        .iter()
+                        .take_while(|l| !l.starts_with("Part ") && !l.is_empty())
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    (answer, timing_str)
+                } else if let Some(rest) = rest.strip_prefix(ANSI_BOLD) {
+                    let (answer, timing_str) = rest.split_once(ANSI_RESET)?;
+                    (answer.to_string(), timing_str)
+                } else {
+                    // NOTE: without colors, the answer is only followed by its timing, e.g. `42 (1.2ms)`.
+                    let (answer, _) = rest.rsplit_once(" (")?;
+                    (answer.to_string(), &rest[answer.len()..])
+                };
+
+                let (duration, samples, cold) = parse_duration(timing_str).unwrap_or_default();
+
+                Some((
+                    part,
+                    PartResult {
+                        answer: Some(answer),
+                        duration,
+                        samples,
+                        cold,
+                        ..PartResult::default()
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Parse a duration suffix like ` (1.2ms)` or ` (1.2ms @ 100 samples, cold 2.0ms)`.
+    /// Returns the duration, the number of samples and the cold duration.
+    fn parse_duration(timing_str: &str) -> Option<(Duration, u128, Duration)> {
+        let inner = timing_str.trim().strip_prefix('(')?.strip_suffix(')')?;
+
+        // NOTE: solutions built before cold timings were introduced do not print them.
+        let (inner, cold) = match inner.split_once(", cold ") {
+            Some((inner, cold)) => (inner, Some(cold)),
+            None => (inner, None),
+        };
+
+        let inner = inner.trim_end_matches(" samples");
+
+        let (str_timing, samples) = match inner.split_once(" @ ") {
+            Some((str_timing, samples)) => (str_timing, samples.parse().ok()?),
+            None => (inner, 1),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let to_duration = |s: &str| parse_nanos(s).map(|nanos| Duration::from_nanos(nanos as u64));
+
+        let duration = to_duration(str_timing)?;
+        let cold = match cold {
+            Some(cold) => to_duration(cold)?,
+            None => duration,
+        };
+
+        Some((duration, samples, cold))
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
-            .split(" samples)")
+            .split(" samples")
             .next()?
             .split('(')
             .next_back()?
@@ -179,12 +981,7 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -205,9 +1002,16 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{build_solutions, parse_exec_time, parse_executable, parse_parts};
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                Pinning,
+                run_multi::{Error, RunOptions},
+            },
+        };
+        use std::{env, fs, process};
 
         #[test]
         fn parses_execution_times() {
@@ -220,8 +1024,73 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1.unwrap().nanos, 74.13_f64);
+            assert_approx_eq!(res.part_2.unwrap().nanos, 74130000_f64);
+        }
+
+        #[test]
+        fn parses_sample_statistics() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.0ns @ 100 samples)".into(),
+                    "@aoc-stats part=1 mean_nanos=74 samples=100 min_nanos=70 max_nanos=90 stddev_nanos=4".into(),
+                    "Part 2: ✖        ".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            let stats = res.part_1.unwrap().stats.unwrap();
+            assert_eq!(stats.samples, 100);
+            assert_approx_eq!(stats.min_nanos, 70_f64);
+            assert_approx_eq!(stats.max_nanos, 90_f64);
+            assert_approx_eq!(stats.stddev_nanos, 4_f64);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_parse_timings_and_memory() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (20.0ns @ 100 samples)".into(),
+                    "@aoc-stats part=0 mean_nanos=20 samples=100 min_nanos=18 max_nanos=25 stddev_nanos=1".into(),
+                    "Part 1: 0 (74.0ns @ 100 samples)".into(),
+                    "@aoc-stats part=1 mean_nanos=74 samples=100 min_nanos=70 max_nanos=90 stddev_nanos=4".into(),
+                    "@aoc-stats peak_memory_bytes=2097152".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_approx_eq!(res.parse.unwrap().nanos, 20_f64);
+            assert_eq!(res.peak_memory_bytes, Some(2_097_152));
+
+            let res = parse_exec_time(
+                &[
+                    "Parse: (20.0ns @ 100 samples)".into(),
+                    "Part 1: 0 (74.0ns @ 100 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_approx_eq!(res.parse.unwrap().nanos, 20_f64);
+        }
+
+        #[test]
+        fn parses_cold_timings() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.0ns @ 100 samples, cold 1.2µs)".into(),
+                    "@aoc-stats part=1 mean_nanos=74 samples=100 min_nanos=70 max_nanos=90 stddev_nanos=4 cold_nanos=1200".into(),
+                    "Part 2: 10 (2.0ms @ 5 samples, cold 3.5ms)".into(),
+                    "@aoc-stats part=2 mean_nanos=2000000 samples=5 min_nanos=1900000 max_nanos=2100000 stddev_nanos=1000".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.part_1.unwrap().cold_nanos.unwrap(), 1200_f64);
+            assert_eq!(res.part_2.unwrap().cold_nanos, None);
         }
 
         #[test]
@@ -235,8 +1104,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1.unwrap().nanos, 2000000000_f64);
+            assert_approx_eq!(res.part_2.unwrap().nanos, 100000000_f64);
         }
 
         #[test]
@@ -253,5 +1122,236 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn ignores_timings_of_unsolved_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖        ".into(),
+                    "@aoc-stats part=1 mean_nanos=1000 samples=10 min_nanos=900 max_nanos=1100 stddev_nanos=50 cold_nanos=2000".into(),
+                    "Part 2: \x1b[1m5\x1b[0m (2.0µs @ 10 samples, cold 3.0µs)".into(),
+                    "@aoc-stats part=2 mean_nanos=2000 samples=10 min_nanos=1900 max_nanos=2100 stddev_nanos=50 cold_nanos=3000".into(),
+                ],
+                day!(1),
+            );
+            assert!(res.part_1.is_none());
+            assert_approx_eq!(res.part_2.unwrap().nanos, 2000_f64);
+            assert_approx_eq!(res.total_nanos, 2000_f64);
+        }
+
+        #[test]
+        fn parses_part_answers() {
+            let res = parse_parts(&[
+                "Part 1: \x1b[1m42\x1b[0m > benching\rPart 1: \x1b[1m42\x1b[0m (1.5ms @ 10 samples)".into(),
+                "Part 2: ▼ (2.0µs)".into(),
+                "#..".into(),
+                "..#".into(),
+                "".into(),
+            ]);
+            assert_eq!(res.len(), 2);
+            assert_eq!(res[0].0, 1);
+            assert_eq!(res[0].1.answer.as_deref(), Some("42"));
+            assert_eq!(res[0].1.duration.as_nanos(), 1_500_000);
+            assert_eq!(res[0].1.samples, 10);
+            assert_eq!(res[1].0, 2);
+            assert_eq!(res[1].1.answer.as_deref(), Some("#..\n..#"));
+            assert_eq!(res[1].1.duration.as_nanos(), 2_000);
+            assert_eq!(res[1].1.samples, 1);
+        }
+
+        #[test]
+        fn parses_cold_part_results() {
+            let res =
+                parse_parts(&["Part 1: \x1b[1m7\x1b[0m (1.5ms @ 10 samples, cold 4.0ms)".into()]);
+            assert_eq!(res[0].1.answer.as_deref(), Some("7"));
+            assert_eq!(res[0].1.duration.as_nanos(), 1_500_000);
+            assert_eq!(res[0].1.samples, 10);
+            assert_eq!(res[0].1.cold.as_nanos(), 4_000_000);
+        }
+
+        #[test]
+        fn parses_uncolored_part_results() {
+            let res = parse_parts(&[
+                "Part 1: a (b) > benching\rPart 1: a (b) (1.5ms @ 10 samples, cold 4.0ms)".into(),
+            ]);
+            assert_eq!(res[0].1.answer.as_deref(), Some("a (b)"));
+            assert_eq!(res[0].1.duration.as_nanos(), 1_500_000);
+            assert_eq!(res[0].1.cold.as_nanos(), 4_000_000);
+        }
+
+        #[test]
+        fn parses_missing_part_answers() {
+            let res = parse_parts(&["Part 1: ✖             ".into()]);
+            assert_eq!(res.len(), 1);
+            assert!(res[0].1.answer.is_none());
+        }
+
+        #[test]
+        fn parses_build_artifacts() {
+            let (day, path) = parse_executable(
+                r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"07"},"executable":"/repo/target/release/07","fresh":true}"#,
+            )
+            .unwrap();
+            assert_eq!(day, day!(7));
+            assert_eq!(path.to_str(), Some("/repo/target/release/07"));
+        }
+
+        #[test]
+        fn skips_other_build_messages() {
+            assert!(parse_executable(r#"{"reason":"build-finished","success":true}"#).is_none());
+            assert!(
+                parse_executable(
+                    r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}"#
+                )
+                .is_none()
+            );
+            assert!(parse_executable("Compiling advent_of_code").is_none());
+        }
+
+        #[test]
+        fn reports_failed_builds() {
+            // NOTE: cargo fails right away in a directory without a manifest.
+            let checkout = env::temp_dir().join(format!("aoc-build-{}", process::id()));
+            fs::create_dir_all(&checkout).unwrap();
+
+            let options = RunOptions {
+                is_release: false,
+                is_timed: false,
+                jobs: 1,
+                timeout: None,
+                pinning: Pinning::default(),
+                profile: None,
+            };
+
+            let result = build_solutions(&[day!(1)], &options, Some(&checkout));
+            assert!(matches!(result, Err(Error::BuildFailed(_))));
+
+            fs::remove_dir_all(&checkout).unwrap();
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayResult, DayStatus, Outcome, OutputLine, RunOptions, run_day, run_days};
+    use crate::template::{Day, Pinning, all_days, timings::Timings};
+    use std::{
+        env, fs,
+        path::Path,
+        process, thread,
+        time::{Duration, Instant},
+    };
+
+    #[test]
+    fn summarizes_statuses() {
+        let day = |day: u8| Day::new(day).unwrap();
+        let outcome = Outcome {
+            days: vec![
+                (day(1), DayResult::from_status(DayStatus::Solved)),
+                (day(2), DayResult::from_status(DayStatus::Failed)),
+                (day(3), DayResult::from_status(DayStatus::Solved)),
+                (day(4), DayResult::from_status(DayStatus::TimedOut)),
+            ],
+            timings: Timings::default(),
+        };
+
+        assert!(!outcome.is_success());
+        assert_eq!(
+            outcome.summary(),
+            vec![
+                "Solved:          2 (01, 03)",
+                "Failed:          1 (02)",
+                "Compile error:   0",
+                "Not scaffolded:  0",
+                "Timed out:       1 (04)",
+            ]
+        );
+
+        let outcome = Outcome {
+            days: vec![(day(1), DayResult::from_status(DayStatus::NotScaffolded))],
+            timings: Timings::default(),
+        };
+        assert!(outcome.is_success());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn classifies_day_status() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let checkout = env::temp_dir().join(format!("aoc-run-multi-{}", process::id()));
+        fs::create_dir_all(checkout.join("src/bin")).unwrap();
+        fs::write(checkout.join("src/bin/01.rs"), "").unwrap();
+
+        let script = |name: &str, body: &str| {
+            let path = checkout.join(name);
+            fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        };
+
+        let options = RunOptions {
+            is_release: true,
+            is_timed: false,
+            jobs: 1,
+            timeout: Some(Duration::from_millis(200)),
+            pinning: Pinning::default(),
+            profile: None,
+        };
+
+        let status = |day: u8, executable: Option<&Path>| {
+            let executable = executable.map(Path::to_path_buf);
+            run_day(
+                Day::new(day).unwrap(),
+                Some(&checkout),
+                executable.as_ref(),
+                &options,
+                &mut |_| {},
+            )
+            .unwrap()
+            .status
+        };
+
+        let solved = script("solved", "echo 'Part 1: 42 (1.0ms)'");
+        let failed = script("failed", "exit 3");
+        // NOTE: `sleep` runs in a child process of the shell, which keeps the output open after the shell is killed.
+        let slow = script("slow", "sleep 5; true");
+
+        assert_eq!(status(1, Some(&solved)), DayStatus::Solved);
+        assert_eq!(status(1, Some(&failed)), DayStatus::Failed);
+        assert_eq!(status(1, None), DayStatus::CompileError);
+        assert_eq!(status(2, Some(&solved)), DayStatus::NotScaffolded);
+
+        let start = Instant::now();
+        assert_eq!(status(1, Some(&slow)), DayStatus::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        fs::remove_dir_all(&checkout).unwrap();
+    }
+
+    #[test]
+    fn runs_parallel_days_in_order() {
+        let days: Vec<Day> = all_days().take(6).collect();
+        let mut started = vec![];
+        let mut finished = vec![];
+
+        run_days(
+            &days,
+            3,
+            |day, emit| {
+                // make earlier days finish last.
+                thread::sleep(Duration::from_millis(u64::from(10 - day.into_inner()) * 5));
+                emit(OutputLine::Stdout(day.to_string()));
+                day
+            },
+            |day| started.push(day),
+            |day, result| {
+                assert_eq!(day, result);
+                finished.push(day);
+            },
+        );
+
+        assert_eq!(started, days);
+        assert_eq!(finished, days);
     }
 }
@@ -1,35 +1,173 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
+use std::io::{self, Write, stdout};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::affinity::{PIN_CPU_ENV, pin_current_thread};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, ansi, aoc_cli, config};
+
+/// Environment variable that makes benched solutions print their sample statistics.
+/// Set by `all` and `time` when running solution binaries.
+pub(crate) const STATS_ENV: &str = "AOC_BENCH_STATS";
+
+/// Prefix of the machine-readable statistics line, see [`STATS_ENV`].
+pub(crate) const STATS_PREFIX: &str = "@aoc-stats ";
+
+/// The answer and timing of a single solution part.
+#[derive(Clone, Debug, Default)]
+pub struct PartResult {
+    pub answer: Option<String>,
+    /// Mean duration of the benched samples, or of the first run if the part was not benched.
+    pub duration: Duration,
+    pub samples: u128,
+    pub spread: Spread,
+    /// Duration of the first run, before caches and the allocator are warmed up.
+    pub cold: Duration,
+}
+
+/// How the samples of a benched part are spread around their mean.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Spread {
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+}
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
+    let is_timed = env::args().any(|x| x == "--time");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, timing) = run_timed(func, input, is_timed, |result| {
+        print_result(result, &part_str, "");
+        if is_timed {
+            print!(" > {}benching{}", ansi(ANSI_ITALIC), ansi(ANSI_RESET));
+            let _ = stdout().flush();
+        }
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_duration(&timing));
 
     if let Some(result) = result {
+        // NOTE: unsolved parts are not reported, so no timing is stored for them.
+        if is_timed {
+            print_stats(part, &timing);
+        }
+
         submit_result(result, day, part);
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run the input parser of a solution, see the `parse` option of [`solution!`](crate::solution).
+/// The parsed value is only used to measure the parser and discarded afterwards.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str) {
+    let is_timed = env::args().any(|x| x == "--time");
+
+    let (_, timing) = run_timed(func, input, is_timed, |_| {});
+    println!("Parse:{}", format_duration(&timing));
+
+    if is_timed {
+        // NOTE: the parser is reported as part 0.
+        print_stats(0, &timing);
+    }
+}
+
+/// Pin the solution to the CPU core passed via [`PIN_CPU_ENV`], see `cargo time --pin-cpu`.
+pub fn pin_cpu() {
+    let Ok(value) = env::var(PIN_CPU_ENV) else {
+        return;
+    };
+
+    let pinned = value
+        .trim()
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "not a CPU index"))
+        .and_then(pin_current_thread);
+
+    if let Err(e) = pinned {
+        eprintln!("Warning: failed to pin to CPU {value}: {e}");
+    }
+}
+
+/// Print the peak memory usage of the solution, if requested via [`STATS_ENV`].
+/// This is the peak resident set size of the process, so it includes the input and the binary itself.
+pub fn print_peak_memory() {
+    if env::var_os(STATS_ENV).is_none() {
+        return;
+    }
+
+    if let Some(bytes) = peak_memory_bytes() {
+        println!("{STATS_PREFIX}peak_memory_bytes={bytes}");
+    }
+}
+
+/// Read the peak resident set size of the current process. Only supported on Linux.
+fn peak_memory_bytes() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+
+    let kilobytes: u64 = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+
+    Some(kilobytes * 1024)
+}
+
+fn print_stats(part: u8, timing: &PartResult) {
+    if env::var_os(STATS_ENV).is_none() {
+        return;
+    }
+
+    println!(
+        "{STATS_PREFIX}part={part} mean_nanos={} samples={} min_nanos={} max_nanos={} stddev_nanos={} cold_nanos={}",
+        timing.duration.as_nanos(),
+        timing.samples,
+        timing.spread.min.as_nanos(),
+        timing.spread.max.as_nanos(),
+        timing.spread.stddev.as_nanos(),
+        timing.cold.as_nanos()
+    );
+}
+
+/// Run a solution part without printing anything and return its answer and timing.
+/// This is used by the in-process solution registry.
+pub fn measure_part<T: Display>(
+    func: impl Fn(&str) -> Option<T>,
+    input: &str,
+    is_timed: bool,
+) -> PartResult {
+    let (result, timing) = run_timed(func, input, is_timed, |_| {});
+
+    PartResult {
+        answer: result.map(|x| x.to_string()),
+        ..timing
+    }
+}
+
+/// Run the input parser of a solution without printing anything and return its timing.
+/// This is used by the in-process solution registry.
+pub fn measure_parse<T>(func: impl Fn(&str) -> T, input: &str, is_timed: bool) -> PartResult {
+    run_timed(func, input, is_timed, |_| {}).1
+}
+
+/// Run a solution part. The behavior differs depending on whether we are benching:
+///  1. by default, the function is executed once.
+///  2. with `--time`, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     The budget and sample limits can be changed in the `[bench]` table of `aoc.toml`.
+///
+/// The duration of the first run is kept as the cold timing in both cases. The returned result has no answer.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, PartResult) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,23 +179,40 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let timing = if is_timed {
+        let (duration, samples, spread) = bench(func, input, &base_time);
+        PartResult {
+            answer: None,
+            duration,
+            samples,
+            spread,
+            cold: base_time,
+        }
     } else {
-        (base_time, 1)
+        PartResult {
+            answer: None,
+            duration: base_time,
+            samples: 1,
+            spread: Spread {
+                min: base_time,
+                max: base_time,
+                stddev: Duration::ZERO,
+            },
+            cold: base_time,
+        }
     };
 
-    (result, run.0, run.1)
+    (result, timing)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128, Spread) {
+    let budget = config::get().bench;
+    let bench_iterations = (budget.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(budget.min_samples, budget.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,10 +222,13 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
+    let average = average_duration(&timers);
+
     (
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
+        Duration::from_nanos(average as u64),
         bench_iterations,
+        spread(&timers, average),
     )
 }
 
@@ -82,45 +240,80 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn spread(numbers: &[Duration], average: u128) -> Spread {
+    let average = average as f64;
+    let variance = numbers
+        .iter()
+        .map(|x| (x.as_nanos() as f64 - average).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+
+    Spread {
+        min: numbers.iter().min().copied().unwrap_or_default(),
+        max: numbers.iter().max().copied().unwrap_or_default(),
+        stddev: Duration::from_nanos(variance.sqrt() as u64),
+    }
+}
+
+/// Format the timing of a part, e.g. ` (1.2ms)` or ` (1.0ms @ 100 samples, cold 2.5ms)` for benched parts.
+pub(crate) fn format_duration(timing: &PartResult) -> String {
+    let PartResult {
+        duration,
+        samples,
+        cold,
+        ..
+    } = timing;
+
+    if *samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} @ {samples} samples, cold {cold:.1?})")
+    }
+}
+
+/// Format the final output of a solution part, as printed by `run_part`.
+pub(crate) fn format_result<T: Display>(
+    result: &Option<T>,
+    part: &str,
+    duration_str: &str,
+) -> String {
+    match result {
+        Some(result) if result.to_string().contains('\n') => {
+            format!("{part}: ▼ {duration_str}\n{result}")
+        }
+        Some(result) => format!(
+            "{part}: {}{result}{}{duration_str}",
+            ansi(ANSI_BOLD),
+            ansi(ANSI_RESET)
+        ),
+        None => format!("{part}: ✖             "),
     }
 }
 
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    if !is_intermediate_result {
+        print!("\r");
+        println!("{}", format_result(result, part, duration_str));
+        return;
+    }
+
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+                print!("{part}: ▼ ");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                print!("{part}: {}{result}{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
             }
         }
         None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+            print!("{part}: ✖");
         }
     }
 }
@@ -0,0 +1,296 @@
+/// Module that resolves the templates `scaffold` creates solution modules from and fills in their placeholders.
+/// Templates ship with the crate or are read from the `templates` directory of the project.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{
+    Day, answers::read_title, aoc_cli::get_year, config, environment::command_output,
+    examples::read_answers, history::format_timestamp,
+};
+
+/// Environment variable holding the author name for the `%AUTHOR%` placeholder. Defaults to `git config user.name`.
+static AUTHOR_ENV: &str = "AOC_AUTHOR";
+static DEFAULT_TEMPLATE: &str = "default";
+
+/// Directory of user templates. A template `foo` is read from `templates/foo.rs.txt`.
+static TEMPLATE_DIR: &str = "templates";
+static TEMPLATE_EXTENSION: &str = ".rs.txt";
+
+/// Placeholder that is replaced with the day number.
+static DAY_PLACEHOLDER: &str = "%DAY_NUMBER%";
+
+/// The values a template's placeholders are replaced with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateContext {
+    pub day: Day,
+    pub year: u16,
+    /// Title of the puzzle, if it was downloaded.
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// Date of scaffolding as `YYYY-MM-DD`.
+    pub date: String,
+    /// Answers to the examples of both parts, if known.
+    pub example_answers: [Option<String>; 2],
+}
+
+impl TemplateContext {
+    /// Collect the context for scaffolding a day now.
+    pub fn collect(day: Day) -> Self {
+        let now = format_timestamp(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        );
+
+        TemplateContext {
+            day,
+            year: get_year().unwrap_or_else(|| now[..4].parse().unwrap_or_default()),
+            title: read_title(day),
+            author: env::var(AUTHOR_ENV)
+                .ok()
+                .filter(|x| !x.trim().is_empty())
+                .or_else(|| command_output("git", &["config", "user.name"])),
+            date: now[..10].to_string(),
+            example_answers: read_answers(day),
+        }
+    }
+
+    /// Replace all placeholders in a template.
+    pub fn render(&self, template: &str) -> String {
+        let day_number = self.day.into_inner().to_string();
+
+        let placeholders = [
+            (DAY_PLACEHOLDER, day_number.clone()),
+            ("%DAY%", self.day.to_string()),
+            ("%YEAR%", self.year.to_string()),
+            (
+                "%TITLE%",
+                self.title
+                    .clone()
+                    .unwrap_or_else(|| format!("Day {day_number}")),
+            ),
+            (
+                "%URL%",
+                format!("https://adventofcode.com/{}/day/{day_number}", self.year),
+            ),
+            ("%AUTHOR%", self.author.clone().unwrap_or_default()),
+            ("%DATE%", self.date.clone()),
+            (
+                "%EXAMPLE_ANSWER_1%",
+                format_answer(self.example_answers[0].as_deref()),
+            ),
+            (
+                "%EXAMPLE_ANSWER_2%",
+                format_answer(self.example_answers[1].as_deref()),
+            ),
+        ];
+
+        placeholders
+            .iter()
+            .fold(template.to_string(), |template, (placeholder, value)| {
+                template.replace(placeholder, value)
+            })
+    }
+}
+
+/// Format an answer as the `Option<u64>` returned by solutions, e.g. `Some(42)`.
+/// Answers that are not numbers can not be compared against such a value and are left out.
+pub(crate) fn format_answer(answer: Option<&str>) -> String {
+    answer
+        .and_then(|x| x.trim().parse::<u64>().ok())
+        .map_or_else(|| "None".into(), |x| format!("Some({x})"))
+}
+
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "default",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt")),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+    (
+        "parsed",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/parsed.txt"
+        )),
+    ),
+];
+
+#[derive(Debug)]
+pub enum Error {
+    /// No built-in or user template with this name exists.
+    Unknown {
+        name: String,
+        available: Vec<String>,
+    },
+    InvalidName(String),
+    IO(PathBuf, io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Unknown { name, available } => write!(
+                f,
+                "unknown template \"{name}\", available templates: {}.",
+                available.join(", ")
+            ),
+            Error::InvalidName(name) => write!(
+                f,
+                "invalid template name \"{name}\", only letters, digits, `-` and `_` are allowed."
+            ),
+            Error::IO(path, e) => write!(f, "failed to read \"{}\": {e}", path.display()),
+        }
+    }
+}
+
+/// The template to use if none is passed, configured via `template` in `aoc.toml` or `AOC_TEMPLATE`.
+pub fn default_name() -> String {
+    config::get()
+        .template
+        .clone()
+        .unwrap_or_else(|| DEFAULT_TEMPLATE.into())
+}
+
+/// Read the template with the given name. User templates take precedence over built-in ones of the same name.
+pub fn load(name: &str) -> Result<String, Error> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(Error::InvalidName(name.into()));
+    }
+
+    let path = user_template_path(name);
+
+    match fs::read_to_string(&path) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(Error::IO(path, e)),
+        Err(_) => {}
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| Error::Unknown {
+            name: name.into(),
+            available: available(),
+        })
+}
+
+/// Names of all built-in and user templates, sorted.
+pub fn available() -> Vec<String> {
+    let user_templates = fs::read_dir(TEMPLATE_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_suffix(TEMPLATE_EXTENSION)
+                .map(str::to_string)
+        });
+
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .chain(user_templates)
+        .collect();
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn user_template_path(name: &str) -> PathBuf {
+    PathBuf::from(TEMPLATE_DIR).join(format!("{name}{TEMPLATE_EXTENSION}"))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BUILTIN_TEMPLATES, DAY_PLACEHOLDER, Error, TemplateContext, available, load};
+    use crate::day;
+
+    #[test]
+    fn loads_builtin_templates() {
+        for (name, _) in BUILTIN_TEMPLATES {
+            let template = load(name).unwrap();
+            assert!(template.contains(DAY_PLACEHOLDER), "{name}");
+            assert!(template.contains("advent_of_code::solution!"), "{name}");
+        }
+
+        assert!(
+            ["default", "graph", "grid", "parsed"]
+                .iter()
+                .all(|name| available().contains(&(*name).to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_templates() {
+        assert!(matches!(load("foo"), Err(Error::Unknown { .. })));
+        assert!(matches!(
+            load("../src/template"),
+            Err(Error::InvalidName(_))
+        ));
+        assert!(matches!(load(""), Err(Error::InvalidName(_))));
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let context = TemplateContext {
+            day: day!(5),
+            year: 2024,
+            title: Some("Print Queue".into()),
+            author: Some("Jane".into()),
+            date: "2024-12-05".into(),
+            example_answers: [Some("143".into()), Some("abc".into())],
+        };
+
+        assert_eq!(
+            context.render(
+                "solution!(%DAY_NUMBER%); // %DAY% %YEAR% %TITLE% <%URL%> %AUTHOR% %DATE% %EXAMPLE_ANSWER_1% %EXAMPLE_ANSWER_2%"
+            ),
+            "solution!(5); // 05 2024 Print Queue <https://adventofcode.com/2024/day/5> Jane 2024-12-05 Some(143) None"
+        );
+    }
+
+    #[test]
+    fn renders_missing_values() {
+        let context = TemplateContext {
+            day: day!(12),
+            year: 2024,
+            title: None,
+            author: None,
+            date: "2024-12-12".into(),
+            example_answers: [None, None],
+        };
+
+        assert_eq!(
+            context.render("%TITLE%|%AUTHOR%|%EXAMPLE_ANSWER_1%"),
+            "Day 12||None"
+        );
+    }
+}
@@ -1,17 +1,83 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::history::{HistoryEntry, MAX_HISTORY_ENTRIES, RunContext};
+use crate::template::runner::PartResult;
+use crate::template::{Day, config};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE: &str = "timings.json";
+
+/// Path of the stored timings, in the data directory configured in `aoc.toml`.
+pub fn timings_path() -> String {
+    config::get().data_path("", TIMINGS_FILE)
+}
+
+/// Path the stored timings are backed up to before they are overwritten.
+pub fn backup_path() -> String {
+    format!("{}.bak", timings_path())
+}
+
+/// Path an unreadable file is kept at before it is overwritten, e.g. `timings.json.corrupt-1733011200`.
+/// Existing files are never replaced, a counter is appended instead.
+fn corrupt_path(path: &str, timestamp: u64) -> String {
+    (0..)
+        .map(|i| match i {
+            0 => format!("{path}.corrupt-{timestamp}"),
+            i => format!("{path}.corrupt-{timestamp}-{i}"),
+        })
+        .find(|path| !Path::new(path).exists())
+        .unwrap_or_default()
+}
+
+fn temp_path() -> String {
+    format!("{}.tmp", timings_path())
+}
+
+/// Version of the `timings.json` schema written by this template.
+/// Version 1 did not have a `version` key and stored parts as display strings like `"74.1ns"`.
+const SCHEMA_VERSION: u8 = 2;
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    /// Timing of the input parser, if the solution declares one. Not part of `total_nanos`.
+    pub parse: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Peak resident set size of the solution binary. Unknown for in-process runs.
+    pub peak_memory_bytes: Option<u64>,
+    /// Past measurements of this day, oldest first.
+    pub history: Vec<HistoryEntry>,
+}
+
+/// Represents the benchmark time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Mean duration in nanoseconds.
+    pub nanos: f64,
+    /// Unknown for timings migrated from version 1 or parsed from solutions that do not report them.
+    pub stats: Option<SampleStats>,
+    /// Duration of the first, unbenched run in nanoseconds.
+    pub cold_nanos: Option<f64>,
+}
+
+/// Statistics over the samples of a benched part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampleStats {
+    pub samples: u64,
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+    pub stddev_nanos: f64,
 }
 
 /// Represents benchmark times for a set of days.
@@ -19,30 +85,157 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// The policy the timings were last merged with.
+    pub policy: MergePolicy,
+}
+
+/// How fresh timings are merged into stored ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Replace stored timings with fresh ones.
+    #[default]
+    Latest,
+    /// Keep the faster timing per part.
+    Best,
+    /// Use the mean of all timings in the history of a day.
+    Mean,
+}
+
+impl MergePolicy {
+    pub fn id(self) -> &'static str {
+        match self {
+            MergePolicy::Latest => "latest",
+            MergePolicy::Best => "best",
+            MergePolicy::Mean => "mean",
+        }
+    }
+}
+
+impl FromStr for MergePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "latest" => Ok(MergePolicy::Latest),
+            "best" => Ok(MergePolicy::Best),
+            "mean" => Ok(MergePolicy::Mean),
+            x => Err(format!(
+                "unknown merge policy \"{x}\", expected `latest`, `best` or `mean`."
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Parser(e) => write!(f, "{} is invalid: {e}", timings_path()),
+            ReadError::IO(e) => write!(f, "could not read {}: {e}", timings_path()),
+        }
+    }
+}
+
+/// Back up the file at `path` before it is overwritten. Readable files are copied to `backup`, replacing the previous
+/// backup. Unreadable files, e.g. ones ignored with `--force`, are kept under their own name instead, see
+/// [`corrupt_path`]. Otherwise, the next store would replace the only copy of the original with the new timings.
+/// Returns the path an unreadable file was kept at.
+fn backup(path: &str, backup: &str, timestamp: u64) -> Result<Option<String>, io::Error> {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let is_readable = String::from_utf8(content).is_ok_and(|json| Timings::try_from(json).is_ok());
+
+    if is_readable {
+        fs::copy(path, backup)?;
+        return Ok(None);
+    }
+
+    let kept = corrupt_path(path, timestamp);
+    fs::copy(path, &kept)?;
+    Ok(Some(kept))
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// An existing file is backed up first, see [`backup`]. The new file is written to a temporary file and then moved
+    /// into place, so an interrupted write never leaves a truncated file behind.
+    /// Returns the path an unreadable existing file was kept at.
+    pub fn store_file(&self) -> Result<Option<String>, io::Error> {
+        let path = timings_path();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs());
+
+        let kept = backup(&path, &backup_path(), timestamp)?;
+
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+
+        {
+            let mut file = fs::File::create(temp_path())?;
+            json.format_to(&mut file)?;
+            file.sync_all()?;
+        }
+
+        fs::rename(temp_path(), path)?;
+        Ok(kept)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    pub fn read_from_file() -> Result<Self, ReadError> {
+        match fs::read_to_string(timings_path()) {
+            Ok(json) => Timings::try_from(json).map_err(ReadError::Parser),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(ReadError::IO(e)),
+        }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
+    /// Merge two sets of timings. Days present in both are combined according to `policy`.
+    /// The history of merged days is kept, capped at [`MAX_HISTORY_ENTRIES`].
+    pub fn merge(&self, new: &Self, policy: MergePolicy) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            let previous = self.get(timing.day);
+
+            if let Some(previous) = previous {
+                timing.history = previous
+                    .history
+                    .iter()
+                    .chain(&timing.history)
+                    .cloned()
+                    .collect();
+            }
+
+            let excess = timing.history.len().saturating_sub(MAX_HISTORY_ENTRIES);
+            timing.history.drain(..excess);
+
+            match (policy, previous) {
+                (MergePolicy::Best, Some(previous)) => {
+                    timing.part_1 = faster(previous.part_1.as_ref(), timing.part_1);
+                    timing.part_2 = faster(previous.part_2.as_ref(), timing.part_2);
+                    timing.parse = faster(previous.parse.as_ref(), timing.parse);
+                    timing.total_nanos = timing.parts_nanos();
+                }
+                (MergePolicy::Mean, Some(_)) => {
+                    timing.part_1 = history_mean(&timing, 1).or(timing.part_1);
+                    timing.part_2 = history_mean(&timing, 2).or(timing.part_2);
+                    timing.total_nanos = timing.parts_nanos();
+                }
+                _ => {}
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,8 +244,24 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|t| t.day);
+        Timings { data, policy }
+    }
+
+    /// Add a history entry for the current measurement of every day.
+    pub fn with_history_entry(&self, context: &RunContext) -> Self {
+        let mut timings = self.clone();
+
+        for timing in &mut timings.data {
+            timing.history.push(HistoryEntry {
+                context: context.clone(),
+                part_1: timing.part_1.clone(),
+                part_2: timing.part_2.clone(),
+                total_nanos: timing.total_nanos,
+            });
+        }
+
+        timings
     }
 
     /// Sum up total duration of timings as millis.
@@ -60,6 +269,11 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Look up the timing of a given day.
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.data.iter().find(|t| t.day == day)
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -67,12 +281,118 @@ impl Timings {
     }
 }
 
+impl Timing {
+    /// Look up the timing of a part.
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Duration of a part in nanoseconds.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        self.part(part).map(|t| t.nanos)
+    }
+
+    /// Sum of the durations of both parts in nanoseconds.
+    fn parts_nanos(&self) -> f64 {
+        self.part_nanos(1).unwrap_or_default() + self.part_nanos(2).unwrap_or_default()
+    }
+}
+
+/// The faster of a stored and a fresh timing. A missing fresh timing keeps the stored one.
+fn faster(stored: Option<&PartTiming>, fresh: Option<PartTiming>) -> Option<PartTiming> {
+    match (stored, fresh) {
+        (Some(stored), Some(fresh)) if stored.nanos < fresh.nanos => Some(stored.clone()),
+        (stored, None) => stored.cloned(),
+        (_, fresh) => fresh,
+    }
+}
+
+/// The mean duration of a part over the history of a day. Sample statistics do not carry over to the mean.
+fn history_mean(timing: &Timing, part: u8) -> Option<PartTiming> {
+    let nanos: Vec<f64> = timing
+        .history
+        .iter()
+        .filter_map(|entry| match part {
+            1 => entry.part_1.as_ref(),
+            _ => entry.part_2.as_ref(),
+        })
+        .map(|t| t.nanos)
+        .collect();
+
+    #[allow(clippy::cast_precision_loss)]
+    (!nanos.is_empty())
+        .then(|| PartTiming::from_nanos(nanos.iter().sum::<f64>() / nanos.len() as f64))
+}
+
+impl From<&PartResult> for PartTiming {
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn from(result: &PartResult) -> Self {
+        PartTiming {
+            nanos: result.duration.as_nanos() as f64,
+            stats: Some(SampleStats {
+                samples: result.samples as u64,
+                min_nanos: result.spread.min.as_nanos() as f64,
+                max_nanos: result.spread.max.as_nanos() as f64,
+                stddev_nanos: result.spread.stddev.as_nanos() as f64,
+            }),
+            cold_nanos: Some(result.cold.as_nanos() as f64),
+        }
+    }
+}
+
+impl PartTiming {
+    /// A timing without sample statistics.
+    pub fn from_nanos(nanos: f64) -> Self {
+        PartTiming {
+            nanos,
+            stats: None,
+            cold_nanos: None,
+        }
+    }
+
+    /// The duration as displayed in the readme, e.g. `74.0ns`.
+    pub fn display(&self) -> String {
+        format_nanos(self.nanos)
+    }
+}
+
+/// Format nanoseconds the way `Duration`'s debug format does, e.g. `74.0ns` or `1.2s`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Parse a duration as printed by `Duration`'s debug format (e.g. `74.13ns` or `1.2s`) into nanoseconds.
+pub fn parse_nanos(str_timing: &str) -> Option<f64> {
+    match str_timing {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
+
+        map.insert("policy".into(), JsonValue::String(value.policy.id().into()));
+
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -86,7 +406,24 @@ impl TryFrom<String> for Timings {
     type Error = String;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        let mut json =
+            JsonValue::from_str(&value).map_err(|e| format!("not valid JSON file: {e}"))?;
+
+        let version = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("version")
+            .map_or(Some(1_f64), |v| v.get::<f64>().copied())
+            .ok_or("expected `json.version` to be a number.")?;
+
+        #[allow(clippy::float_cmp)]
+        if version == 1_f64 {
+            migrate_from_v1(&mut json)?;
+        } else if version != f64::from(SCHEMA_VERSION) {
+            return Err(format!(
+                "unsupported schema version {version}, expected {SCHEMA_VERSION}."
+            ));
+        }
 
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
@@ -96,15 +433,69 @@ impl TryFrom<String> for Timings {
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // NOTE: files stored before merge policies were introduced always used the latest timings.
+        let policy = match json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|map| map.get("policy"))
+        {
+            None => MergePolicy::Latest,
+            Some(v) => v
+                .get::<String>()
+                .ok_or("expected `json.policy` to be a string.")?
+                .parse()?,
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            policy,
         })
     }
 }
 
+/// Migrate a version 1 document in place by replacing the display strings of parts with part objects.
+fn migrate_from_v1(json: &mut JsonValue) -> Result<(), String> {
+    let Some(JsonValue::Array(data)) = json
+        .get_mut::<HashMap<String, JsonValue>>()
+        .and_then(|map| map.get_mut("data"))
+    else {
+        // NOTE: let the regular deserialization report malformed documents.
+        return Ok(());
+    };
+
+    for timing in data.iter_mut() {
+        let Some(timing) = timing.get_mut::<HashMap<String, JsonValue>>() else {
+            continue;
+        };
+
+        migrate_parts_from_v1(timing)?;
+
+        if let Some(JsonValue::Array(history)) = timing.get_mut("history") {
+            for entry in history.iter_mut() {
+                if let Some(entry) = entry.get_mut::<HashMap<String, JsonValue>>() {
+                    migrate_parts_from_v1(entry)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_parts_from_v1(map: &mut HashMap<String, JsonValue>) -> Result<(), String> {
+    for key in ["part_1", "part_2"] {
+        if let Some(JsonValue::String(str_timing)) = map.get(key) {
+            let nanos = parse_nanos(str_timing)
+                .ok_or_else(|| format!("could not migrate timing \"{str_timing}\"."))?;
+            map.insert(key.into(), JsonValue::from(&PartTiming::from_nanos(nanos)));
+        }
+    }
+
+    Ok(())
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<&Timing> for JsonValue {
@@ -113,24 +504,21 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        map.insert("part_1".into(), optional_part(value.part_1.as_ref()));
+        map.insert("part_2".into(), optional_part(value.part_2.as_ref()));
+        map.insert("parse".into(), optional_part(value.parse.as_ref()));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
+        #[allow(clippy::cast_precision_loss)]
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "peak_memory_bytes".into(),
+            value
+                .peak_memory_bytes
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
         );
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
         );
 
         JsonValue::Object(map)
@@ -153,24 +541,125 @@ impl TryFrom<&JsonValue> for Timing {
 
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .ok_or("Expected timing.part_1 to be null or an object.".to_string())
+            .and_then(parse_optional_part)?;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .ok_or("Expected timing.part_2 to be null or an object.".to_string())
+            .and_then(parse_optional_part)?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: `parse` and `peak_memory_bytes` are optional and missing in older files.
+        let parse = parse_optional_part(json.get("parse").unwrap_or(&JsonValue::Null))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let peak_memory_bytes = match json.get("peak_memory_bytes") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::Number(x)) => Some(*x as u64),
+            Some(_) => {
+                return Err("Expected timing.peak_memory_bytes to be null or a number.".into());
+            }
+        };
+
+        // NOTE: timings stored before the history was introduced do not have one.
+        let history = match json.get("history") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.history to be an array.")?
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1,
+            part_2,
+            parse,
             total_nanos,
+            peak_memory_bytes,
+            history,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Serialize an optional string, using `null` for missing values.
+pub fn optional_string(value: Option<&String>) -> JsonValue {
+    value.map_or(JsonValue::Null, |x| JsonValue::String(x.clone()))
+}
+
+/// Serialize an optional part timing, using `null` for missing parts.
+pub fn optional_part(value: Option<&PartTiming>) -> JsonValue {
+    value.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/// Deserialize an optional part timing, accepting `null` for missing parts.
+pub fn parse_optional_part(value: &JsonValue) -> Result<Option<PartTiming>, String> {
+    if value.is_null() {
+        Ok(None)
+    } else {
+        PartTiming::try_from(value).map(Some)
+    }
+}
+
+impl From<&PartTiming> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+
+        if let Some(stats) = value.stats {
+            map.insert("samples".into(), JsonValue::Number(stats.samples as f64));
+            map.insert("min_nanos".into(), JsonValue::Number(stats.min_nanos));
+            map.insert("max_nanos".into(), JsonValue::Number(stats.max_nanos));
+            map.insert("stddev_nanos".into(), JsonValue::Number(stats.stddev_nanos));
+        }
+
+        if let Some(cold_nanos) = value.cold_nanos {
+            map.insert("cold_nanos".into(), JsonValue::Number(cold_nanos));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>().copied());
+
+        let nanos = number("nanos").ok_or("Expected part.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let stats = match number("samples") {
+            None => None,
+            Some(samples) => Some(SampleStats {
+                samples: samples as u64,
+                min_nanos: number("min_nanos").ok_or("Expected part.min_nanos to be a number.")?,
+                max_nanos: number("max_nanos").ok_or("Expected part.max_nanos to be a number.")?,
+                stddev_nanos: number("stddev_nanos")
+                    .ok_or("Expected part.stddev_nanos to be a number.")?,
+            }),
+        };
+
+        Ok(PartTiming {
+            nanos,
+            stats,
+            cold_nanos: number("cold_nanos"),
         })
     }
 }
@@ -181,44 +670,117 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings, backup};
+    use std::{env, fs, process};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn keeps_unreadable_files_apart_from_backup() {
+        let dir = env::temp_dir().join(format!("aoc-timings-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("timings.json").display().to_string();
+        let bak = format!("{path}.bak");
+
+        fs::write(&path, "{ corrupt").unwrap();
+        assert_eq!(
+            backup(&path, &bak, 42).unwrap(),
+            Some(format!("{path}.corrupt-42"))
+        );
+        assert!(!fs::exists(&bak).unwrap());
+
+        // NOTE: the corrupt file was replaced by valid timings, which are now backed up as usual.
+        let valid = JsonValue::from(get_mock_timings()).stringify().unwrap();
+        fs::write(&path, &valid).unwrap();
+        assert_eq!(backup(&path, &bak, 42).unwrap(), None);
+        assert_eq!(fs::read_to_string(&bak).unwrap(), valid);
+        assert_eq!(
+            fs::read_to_string(format!("{path}.corrupt-42")).unwrap(),
+            "{ corrupt"
+        );
+
+        // NOTE: kept files are never replaced.
+        fs::write(&path, "{ corrupt again").unwrap();
+        assert_eq!(
+            backup(&path, &bak, 42).unwrap(),
+            Some(format!("{path}.corrupt-42-1"))
+        );
+        assert_eq!(fs::read_to_string(&bak).unwrap(), valid);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming::from_nanos(10_000_000_f64)),
+                    part_2: Some(PartTiming::from_nanos(20_000_000_f64)),
+                    parse: None,
                     total_nanos: 3e+10,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming::from_nanos(30_000_000_f64)),
+                    part_2: Some(PartTiming::from_nanos(40_000_000_f64)),
+                    parse: None,
                     total_nanos: 7e+10,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(PartTiming::from_nanos(40_000_000_f64)),
                     part_2: None,
+                    parse: None,
                     total_nanos: 4e+10,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 },
             ],
+            ..Timings::default()
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{MergePolicy, PartTiming, SampleStats, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1500, "samples": 10, "min_nanos": 1000, "max_nanos": 2000, "stddev_nanos": 250, "cold_nanos": 3000 }, "part_2": { "nanos": 20 }, "total_nanos": 1520 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    nanos: 1500_f64,
+                    stats: Some(SampleStats {
+                        samples: 10,
+                        min_nanos: 1000_f64,
+                        max_nanos: 2000_f64,
+                        stddev_nanos: 250_f64,
+                    }),
+                    cold_nanos: Some(3000_f64),
+                })
+            );
+            assert_eq!(timing.part_2, Some(PartTiming::from_nanos(20_f64)));
+            assert_eq!(timing.part_1.as_ref().unwrap().display(), "1.5µs");
+        }
+
+        #[test]
+        fn migrates_version_1_timings() {
             let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(PartTiming::from_nanos(1_000_000_f64)));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
@@ -230,6 +792,44 @@ mod tests {
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        fn migrates_version_1_history() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "2.5µs", "part_2": null, "total_nanos": 2500, "history": [{ "timestamp": 0, "commit": null, "rustc": null, "profile": "release", "part_1": "3.0µs", "part_2": null, "total_nanos": 3000 }] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1, Some(PartTiming::from_nanos(2500_f64)));
+            assert_eq!(
+                timing.history[0].part_1,
+                Some(PartTiming::from_nanos(3000_f64))
+            );
+        }
+
+        #[test]
+        fn reports_parser_errors() {
+            let json = r#"{ "data": [ oops"#.to_string();
+            let error = Timings::try_from(json).unwrap_err();
+            assert!(error.contains("line:1, col:12"), "{error}");
+        }
+
+        #[test]
+        fn reads_merge_policy() {
+            let json = r#"{ "version": 2, "policy": "best", "data": [] }"#.to_string();
+            assert_eq!(Timings::try_from(json).unwrap().policy, MergePolicy::Best);
+
+            let json = r#"{ "version": 2, "data": [] }"#.to_string();
+            assert_eq!(Timings::try_from(json).unwrap().policy, MergePolicy::Latest);
+
+            let json = r#"{ "version": 2, "policy": "fastest", "data": [] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -271,7 +871,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         #[test]
@@ -279,13 +879,17 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(PartTiming::from_nanos(1_000_000_f64)),
+                    part_2: Some(PartTiming::from_nanos(2_000_000_f64)),
+                    parse: None,
                     total_nanos: 3_000_000_000_f64,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 }],
+                ..Timings::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +897,17 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(PartTiming::from_nanos(1_000_000_f64)),
                     part_2: None,
+                    parse: None,
                     total_nanos: 1_000_000_000_f64,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 }],
+                ..Timings::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,18 +917,25 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0.0,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 }],
+                ..Timings::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                history::{MAX_HISTORY_ENTRIES, RunContext},
+                timings::{MergePolicy, PartTiming, Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -333,10 +948,14 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0_f64,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 }],
+                ..Timings::default()
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest);
             assert_eq!(merged.data.len(), 4);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
@@ -353,10 +972,14 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0_f64,
+                    peak_memory_bytes: None,
+                    history: vec![],
                 }],
+                ..Timings::default()
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
@@ -365,11 +988,42 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_capped_history() {
+            let context = RunContext::default();
+            let mut timings = get_mock_timings().with_history_entry(&context);
+
+            for _ in 0..MAX_HISTORY_ENTRIES {
+                timings = timings.merge(
+                    &get_mock_timings().with_history_entry(&context),
+                    MergePolicy::Latest,
+                );
+            }
+
+            assert_eq!(timings.data[0].history.len(), MAX_HISTORY_ENTRIES);
+
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(3),
+                    part_1: None,
+                    part_2: None,
+                    parse: None,
+                    total_nanos: 0_f64,
+                    peak_memory_bytes: None,
+                    history: vec![],
+                }],
+                ..Timings::default()
+            };
+            let merged = timings.merge(&other, MergePolicy::Latest);
+            assert_eq!(merged.data[2].day, day!(3));
+            assert!(merged.data[2].history.is_empty());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
             let other = get_mock_timings();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest);
             assert_eq!(merged.data.len(), 3);
         }
 
@@ -377,8 +1031,57 @@ mod tests {
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
             let other = Timings::default();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest);
             assert_eq!(merged.data.len(), 3);
         }
+
+        fn day_1(part_1: f64, part_2: Option<f64>) -> Timings {
+            Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some(PartTiming::from_nanos(part_1)),
+                    part_2: part_2.map(PartTiming::from_nanos),
+                    parse: None,
+                    total_nanos: part_1 + part_2.unwrap_or_default(),
+                    peak_memory_bytes: None,
+                    history: vec![],
+                }],
+                ..Timings::default()
+            }
+        }
+
+        #[test]
+        fn keeps_best_timings() {
+            let timings = get_mock_timings();
+            let merged = timings.merge(&day_1(5_000_000_f64, None), MergePolicy::Best);
+
+            assert_eq!(merged.policy, MergePolicy::Best);
+            assert_eq!(merged.data[0].part_nanos(1), Some(5_000_000_f64));
+            assert_eq!(merged.data[0].part_nanos(2), Some(20_000_000_f64));
+            assert_eq!(merged.data[0].total_nanos, 25_000_000_f64);
+
+            let merged = merged.merge(
+                &day_1(6_000_000_f64, Some(10_000_000_f64)),
+                MergePolicy::Best,
+            );
+            assert_eq!(merged.data[0].part_nanos(1), Some(5_000_000_f64));
+            assert_eq!(merged.data[0].part_nanos(2), Some(10_000_000_f64));
+        }
+
+        #[test]
+        fn averages_history_timings() {
+            let context = RunContext::default();
+            let timings = get_mock_timings().with_history_entry(&context);
+
+            let merged = timings.merge(
+                &day_1(20_000_000_f64, Some(40_000_000_f64)).with_history_entry(&context),
+                MergePolicy::Mean,
+            );
+
+            assert_eq!(merged.data[0].part_nanos(1), Some(15_000_000_f64));
+            assert_eq!(merged.data[0].part_nanos(2), Some(30_000_000_f64));
+            assert_eq!(merged.data[0].total_nanos, 45_000_000_f64);
+            assert_eq!(merged.data[1].part_nanos(1), Some(30_000_000_f64));
+        }
     }
 }
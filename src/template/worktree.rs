@@ -0,0 +1,177 @@
+/// Wrapper module around `git worktree`, used to check out another revision next to the working tree.
+use std::{
+    env,
+    ffi::OsStr,
+    fmt::Display,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Number of worktrees checked out by this process, to give each its own path.
+static CHECKOUTS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug)]
+pub enum GitError {
+    CommandNotFound,
+    BadExitStatus(String),
+}
+
+impl Display for GitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitError::CommandNotFound => write!(f, "git is not present in environment."),
+            GitError::BadExitStatus(stderr) => {
+                write!(f, "git exited with a non-zero status: {stderr}")
+            }
+        }
+    }
+}
+
+/// A temporary, detached checkout of a git revision. The worktree is removed again when dropped.
+pub struct Worktree {
+    /// The repository the worktree belongs to.
+    repo: PathBuf,
+    path: PathBuf,
+}
+
+impl Worktree {
+    /// Check out `rev` of the current repository into a new worktree in the system's temp directory.
+    pub fn checkout(rev: &str) -> Result<Self, GitError> {
+        Self::checkout_in(Path::new("."), rev)
+    }
+
+    fn checkout_in(repo: &Path, rev: &str) -> Result<Self, GitError> {
+        let path = env::temp_dir().join(format!(
+            "aoc-baseline-{}-{}",
+            process::id(),
+            CHECKOUTS.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        git(
+            repo,
+            &[
+                OsStr::new("worktree"),
+                OsStr::new("add"),
+                OsStr::new("--detach"),
+                OsStr::new("--quiet"),
+                path.as_os_str(),
+                OsStr::new(rev),
+            ],
+        )?;
+
+        Ok(Self {
+            repo: repo.into(),
+            path,
+        })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let result = git(
+            &self.repo,
+            &[
+                OsStr::new("worktree"),
+                OsStr::new("remove"),
+                OsStr::new("--force"),
+                self.path.as_os_str(),
+            ],
+        );
+
+        if let Err(e) = result {
+            eprintln!(
+                "Failed to remove worktree {}: {e}\nRun `git worktree prune` to clean it up.",
+                self.path.display()
+            );
+        }
+    }
+}
+
+fn git(repo: &Path, args: &[&OsStr]) -> Result<(), GitError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|_| GitError::CommandNotFound)?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(GitError::BadExitStatus(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{GitError, Worktree, git};
+    use std::{env, ffi::OsStr, fs, path::PathBuf, process};
+
+    /// Create a throwaway repository with a single commit, so tests do not touch the repository they run in.
+    fn init_repo(name: &str) -> PathBuf {
+        let repo = env::temp_dir().join(format!("aoc-worktree-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&repo);
+        fs::create_dir_all(&repo).unwrap();
+        fs::write(repo.join("Cargo.toml"), "").unwrap();
+
+        for args in [
+            vec!["init", "--quiet"],
+            vec!["add", "Cargo.toml"],
+            vec![
+                "-c",
+                "user.name=aoc",
+                "-c",
+                "user.email=aoc@localhost",
+                "-c",
+                "commit.gpgsign=false",
+                "commit",
+                "--quiet",
+                "--message",
+                "init",
+            ],
+        ] {
+            let args: Vec<&OsStr> = args.iter().map(OsStr::new).collect();
+            git(&repo, &args).unwrap();
+        }
+
+        repo
+    }
+
+    #[test]
+    fn checks_out_and_removes_revision() {
+        let repo = init_repo("checkout");
+
+        let first = Worktree::checkout_in(&repo, "HEAD").unwrap();
+        let second = Worktree::checkout_in(&repo, "HEAD").unwrap();
+        assert_ne!(first.path(), second.path());
+        assert!(first.path().join("Cargo.toml").exists());
+
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+        drop(second);
+
+        fs::remove_dir_all(&repo).unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_revision() {
+        let repo = init_repo("unknown");
+
+        assert!(matches!(
+            Worktree::checkout_in(&repo, "no-such-revision"),
+            Err(GitError::BadExitStatus(_))
+        ));
+
+        fs::remove_dir_all(&repo).unwrap();
+    }
+}